@@ -0,0 +1,52 @@
+#[macro_use]
+extern crate criterion;
+extern crate efloat;
+
+use criterion::{black_box, Criterion};
+use efloat::EFloat32;
+
+// A Horner evaluation of a short polynomial over many points, the sort of
+// inner loop `EFloat32Fast` is meant for.  Benchmarks build without debug
+// assertions, so run with `--features precise-tracking` to include the cost
+// of the precise shadow that the fast path avoids.
+const COEFFS: [f32; 6] = [0.5, -1.25, 2.0, 0.125, -0.75, 1.0];
+
+fn horner(c: &mut Criterion) {
+    let xs: Vec<EFloat32> = (0..1000)
+        .map(|i| EFloat32::new_with_err(i as f32 / 1000.0, 1e-6))
+        .collect();
+    let coeffs: Vec<EFloat32> = COEFFS.iter().map(|&c| EFloat32::new(c)).collect();
+
+    c.bench_function("horner EFloat32", |b| {
+        b.iter(|| {
+            let mut total = EFloat32::new(0.0);
+            for &x in black_box(&xs).iter() {
+                let mut acc = coeffs[0];
+                for &k in coeffs[1..].iter() {
+                    acc = acc * x + k;
+                }
+                total = total + acc;
+            }
+            total
+        })
+    });
+
+    let fast_xs: Vec<_> = xs.iter().map(|x| x.fast()).collect();
+    let fast_coeffs: Vec<_> = coeffs.iter().map(|c| c.fast()).collect();
+    c.bench_function("horner EFloat32Fast", |b| {
+        b.iter(|| {
+            let mut total = EFloat32::new(0.0).fast();
+            for &x in black_box(&fast_xs).iter() {
+                let mut acc = fast_coeffs[0];
+                for &k in fast_coeffs[1..].iter() {
+                    acc = acc * x + k;
+                }
+                total = total + acc;
+            }
+            total
+        })
+    });
+}
+
+criterion_group!(benches, horner);
+criterion_main!(benches);
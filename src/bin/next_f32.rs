@@ -1,3 +1,5 @@
+#![forbid(unsafe_code)]
+
 extern crate efloat;
 
 use std::env;
@@ -6,13 +8,13 @@ fn main() {
     let arg1 = env::args().skip(1).next().unwrap();
 
     let f: f32 = arg1.parse::<f32>().unwrap();
-    let i: i32 = unsafe { ::std::mem::transmute(f) };
+    let i: u32 = f.to_bits();
 
     let up: f32 = efloat::next_f32_up(f);
-    let upi: i32 = unsafe { ::std::mem::transmute(up) };
+    let upi: u32 = up.to_bits();
 
     let down: f32 = efloat::next_f32_down(f);
-    let downi: i32 = unsafe { ::std::mem::transmute(down) };
+    let downi: u32 = down.to_bits();
 
     println!("f32: {} = 0x{:x}", f, i);
     println!("Next f32 up: {} = 0x{:x}", up, upi);
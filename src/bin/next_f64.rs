@@ -1,3 +1,5 @@
+#![forbid(unsafe_code)]
+
 use std::env;
 
 fn main() {
@@ -10,11 +12,11 @@ fn main() {
 }
 
 fn f64_to_bits(f: f64) -> u64 {
-    unsafe { ::std::mem::transmute(f) }
+    f.to_bits()
 }
 
 fn bits_to_f64(u: u64) -> f64 {
-    unsafe { ::std::mem::transmute(u) }
+    f64::from_bits(u)
 }
 
 fn next_f64_up(f: f64) -> f64 {
@@ -0,0 +1,83 @@
+use efloat32::EFloat32;
+use num_traits::Float;
+
+/// An axis-aligned bounding box whose faces are `EFloat32`s, as in pbrt's
+/// `Bounds3`, for points whose coordinates are uncertain.
+///
+/// Each face is itself an interval, since which point determines it may be
+/// ambiguous.  The lower bound of each `min` face is at or below the lower
+/// bound of every point's coordinate, and the upper bound of each `max` face
+/// is at or above the upper bound of every point's coordinate, so the box
+/// `[min.lower_bound(), max.upper_bound()]` contains every point wherever it
+/// really is within its intervals.
+#[derive(Debug, Clone, Copy)]
+pub struct Bounds3 {
+    pub min: [EFloat32; 3],
+    pub max: [EFloat32; 3],
+}
+
+impl Bounds3 {
+    /// The box around a single point.
+    pub fn from_point(p: [EFloat32; 3]) -> Bounds3 {
+        Bounds3 { min: p, max: p }
+    }
+
+    /// The box around all of the points, or `None` if there are none.
+    pub fn from_points<I: IntoIterator<Item = [EFloat32; 3]>>(points: I) -> Option<Bounds3> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Bounds3::from_point(first), |b, p| b.union_point(p)))
+    }
+
+    /// Expand the box to include `p`, using the interval minimum and maximum
+    /// (see `Float::min` and `Float::max`) on each axis.
+    pub fn union_point(&self, p: [EFloat32; 3]) -> Bounds3 {
+        let mut b = *self;
+        for (i, &c) in p.iter().enumerate() {
+            b.min[i] = Float::min(self.min[i], c);
+            b.max[i] = Float::max(self.max[i], c);
+        }
+        b
+    }
+
+    /// Whether every position `p` might take (within its intervals) is
+    /// definitely inside the box.
+    pub fn contains(&self, p: [EFloat32; 3]) -> bool {
+        (0..3).all(|i| {
+            self.min[i].lower_bound() <= p[i].lower_bound()
+                && p[i].upper_bound() <= self.max[i].upper_bound()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bounds3() {
+        let p = |x: f32, y: f32, z: f32, err: f32| {
+            [
+                EFloat32::new_with_err(x, err),
+                EFloat32::new_with_err(y, err),
+                EFloat32::new_with_err(z, err),
+            ]
+        };
+        let points = [
+            p(0.0, 1.0, 2.0, 0.1),
+            p(-1.0, 0.5, 2.05, 0.01),
+            p(3.0, -2.0, 1.0, 0.5),
+            p(0.5, 0.5, 0.5, 0.0),
+        ];
+        let b = Bounds3::from_points(points.iter().cloned()).unwrap();
+        for &pt in points.iter() {
+            assert!(b.contains(pt));
+        }
+        assert!(!b.contains(p(3.0, 0.0, 0.0, 1.0)));
+
+        // which point has the largest z is ambiguous, and the face says so
+        assert!(b.max[2].lower_bound() < 2.05 && b.max[2].upper_bound() > 2.06);
+
+        assert!(Bounds3::from_points(Vec::new()).is_none());
+    }
+}
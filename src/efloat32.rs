@@ -1,31 +1,133 @@
+#[cfg(all(
+    feature = "bytemuck",
+    not(any(debug_assertions, feature = "precise-tracking"))
+))]
+use bytemuck::{Pod, Zeroable};
+use error::{EFloatError, ProbabilityError};
 use float_cmp::ApproxEq;
+#[cfg(feature = "half")]
+use half::f16;
+#[cfg(feature = "num-rational")]
+use num_rational::Ratio;
 use num_traits::cast::{NumCast, ToPrimitive};
-use num_traits::{Num, One, ParseFloatError, Zero};
+use num_traits::{Float, FloatErrorKind, Num, One, ParseFloatError, Zero};
+#[cfg(feature = "rand")]
+use rand::Rng;
 use std::cmp::Ordering;
-use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::f64::consts::PI;
+use std::fmt;
+use std::iter::Sum;
+use std::num::FpCategory;
+use std::ops::{Add, Div, Mul, Neg, RangeInclusive, Rem, Sub};
+use std::str::FromStr;
 
 /// This is a floating point type that remembers how far off it might be from the
 /// actual precise value, based on it's history.  It keeps and upper and lower error
 /// bound internally, and you can check those with function calls.
+///
+/// With debug assertions or the `precise-tracking` feature, it also carries a
+/// `precise: f64` shadow of the value, and every operation checks that the
+/// bounds still contain it.
+///
+/// When the bounds of `+`, `-`, `*` or `/` would hit an IEEE indeterminate
+/// form (`inf - inf`, `inf / inf`, ...), the affected bound becomes the
+/// corresponding infinity rather than NaN.
+///
+/// With the `peak-error` feature, it also remembers the worst relative error
+/// estimate of any value in its history, see `peak_relative_error()`.
+///
+/// With the `initial-error` feature, it also remembers the total width its
+/// inputs had when they were constructed, see `propagated_error()`.
+///
+/// The layout is `#[repr(C)]` so that, with the `bytemuck` feature, slices of
+/// these can be cast to and from bytes.  That is only possible without precise
+/// tracking: the `precise: f64` field would leave padding after the three
+/// `f32`s, so `Pod` is not implemented when it is present.
+#[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct EFloat32 {
     v: f32,
     low: f32,
     high: f32,
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
     precise: f64,
+    #[cfg(feature = "peak-error")]
+    max_rel_seen: f32,
+    #[cfg(feature = "initial-error")]
+    initial_error: f32,
+}
+
+/// A plain copy of everything an `EFloat32` knows about itself, for logging
+/// and diagnostics.  `precise` is `Some` only when precise tracking is
+/// compiled in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Snapshot {
+    pub value: f32,
+    pub low: f32,
+    pub high: f32,
+    pub absolute_error: f32,
+    pub precise: Option<f64>,
+}
+
+/// How `EFloat32::format_with` presents the error of a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorStyle {
+    /// The value and the larger distance to either bound: `1.25 ± 0.01`.
+    /// This is also what `Display` prints, and `FromStr` can read it back.
+    PlusMinus,
+    /// Just the bounds: `[1.24, 1.26]`.
+    Interval,
+    /// The value and the larger distance to either bound, counted in
+    /// representable `f32`s: `1.25 (3 ulp)`.
+    Ulps,
+    /// The value and its `relative_error_estimate()` as a percentage:
+    /// `1.25 (0.8%)`.
+    Percent,
+}
+
+/// How a value imported with `EFloat32::new_from_rounded` was rounded to
+/// `f32`, which decides on which side of it the true value may lie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Rounded to nearest, so the true value is within half an ulp either
+    /// way.
+    Nearest,
+    /// Truncated, so the true value is at or further from zero.
+    TowardZero,
+    /// Rounded toward positive infinity, so the true value is at or below.
+    Up,
+    /// Rounded toward negative infinity, so the true value is at or above.
+    Down,
+}
+
+/// What is known about the sign of an `EFloat32`, from `EFloat32::sign()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntervalSign {
+    /// The whole interval is above zero.
+    Positive,
+    /// The whole interval is below zero.
+    Negative,
+    /// The interval is exactly zero.
+    Zero,
+    /// Anything else: the interval includes zero and something else, so the
+    /// sign is unknown (or the bounds are NaN).
+    Straddling,
 }
 
 impl EFloat32 {
     pub fn new(v: f32) -> EFloat32 {
         let ef = EFloat32 {
-            v: v,
+            v,
             low: v,
             high: v,
-            #[cfg(debug_assertions)]
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
             precise: v as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: 0.0,
         };
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
         {
             ef.check();
         }
@@ -33,21 +135,144 @@ impl EFloat32 {
     }
 
     pub fn new_with_err(v: f32, err: f32) -> EFloat32 {
+        let low = next_f32_down(v - err);
+        let high = next_f32_up(v + err);
         let ef = EFloat32 {
-            v: v,
-            low: next_f32_down(v - err),
-            high: next_f32_up(v + err),
-            #[cfg(debug_assertions)]
+            v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
             precise: v as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: high - low,
         };
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
         {
             ef.check();
         }
         ef
     }
 
-    #[cfg(debug_assertions)]
+    /// A value known to be within `ulps` representable steps of `v` in either
+    /// direction, e.g. the result of a library function documented as
+    /// accurate to 2 ULPs.
+    pub fn new_with_ulp_err(v: f32, ulps: u32) -> EFloat32 {
+        let (low, high) = if ulps == 0 || v.is_nan() {
+            (v, v)
+        } else {
+            let o = ordered_f32(v);
+            (
+                from_ordered_f32(o - ulps as i64),
+                from_ordered_f32(o + ulps as i64),
+            )
+        };
+        let ef = EFloat32 {
+            v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: v as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: high - low,
+        };
+        ef.check();
+        ef
+    }
+
+    /// A value that was rounded to `f32` elsewhere in the given `mode`, so
+    /// the true value is within an ulp of `v` on the side the rounding could
+    /// have come from: `Down` gives `[v, next_f32_up(v)]`, `Up` gives
+    /// `[next_f32_down(v), v]`, `TowardZero` gives whichever of those points
+    /// away from zero, and `Nearest` gives both.
+    pub fn new_from_rounded(v: f32, mode: RoundingMode) -> EFloat32 {
+        let (low, high) = match mode {
+            RoundingMode::Nearest => (next_f32_down(v), next_f32_up(v)),
+            RoundingMode::Down => (v, next_f32_up(v)),
+            RoundingMode::Up => (next_f32_down(v), v),
+            RoundingMode::TowardZero => {
+                if v.is_sign_negative() {
+                    (next_f32_down(v), v)
+                } else {
+                    (v, next_f32_up(v))
+                }
+            }
+        };
+        let ef = EFloat32 {
+            v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: v as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: high - low,
+        };
+        ef.check();
+        ef
+    }
+
+    /// The mean of repeated measurements, with bounds of one standard error
+    /// of the mean either side (`s / sqrt(n)`, with the sample standard
+    /// deviation `s`).  Unlike every other constructor the interval is then a
+    /// statistical statement, roughly a 68% confidence interval, not a hard
+    /// bound; the full min-to-max spread of the samples would be safer but
+    /// doesn't shrink as more samples are taken.
+    ///
+    /// A single sample has no spread to estimate from and is taken as exact.
+    /// No samples gives NaN.
+    pub fn from_samples(samples: &[f32]) -> EFloat32 {
+        let n = samples.len() as f64;
+        match samples.len() {
+            0 => return EFloat32::nan(),
+            1 => return EFloat32::new(samples[0]),
+            _ => {}
+        }
+        let mean = samples.iter().map(|&x| x as f64).sum::<f64>() / n;
+        let variance = samples
+            .iter()
+            .map(|&x| (x as f64 - mean) * (x as f64 - mean))
+            .sum::<f64>()
+            / (n - 1.0);
+        let sem = (variance / n).sqrt();
+        let low = f64_to_f32_down(mean - sem);
+        let high = f64_to_f32_up(mean + sem);
+        let ef = EFloat32 {
+            v: mean as f32,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: mean,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: high - low,
+        };
+        ef.check();
+        ef
+    }
+
+    /// Like `new`, but usable in constants and statics.  There is nothing to
+    /// check for an exact value, so this skips `check()`.
+    pub const fn new_exact(v: f32) -> EFloat32 {
+        EFloat32 {
+            v,
+            low: v,
+            high: v,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: v as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: 0.0,
+        }
+    }
+
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
     pub fn new_with_precise_err(v: f32, p: f64, err: f32) -> EFloat32 {
         let mut ef = Self::new_with_err(v, err);
         ef.precise = p;
@@ -62,7 +287,7 @@ impl EFloat32 {
         {
             assert!(self.low <= self.high);
         }
-        #[cfg(debug_assertions)]
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
         {
             if !self.v.is_infinite() && !self.v.is_nan() {
                 assert!(self.low as f64 <= self.precise);
@@ -87,23 +312,182 @@ impl EFloat32 {
         self.high - self.low
     }
 
-    #[cfg(debug_assertions)]
+    /// Whether the value is known exactly, i.e. the interval has no width.
+    pub fn is_exact(&self) -> bool {
+        self.low == self.high
+    }
+
+    // Whether the value or either bound is NaN.
+    fn has_nan(&self) -> bool {
+        self.v.is_nan() || self.low.is_nan() || self.high.is_nan()
+    }
+
+    /// Whether the value is known to be exactly zero (of either sign), i.e.
+    /// both bounds are zero.  This is stricter than `Zero::is_zero`, which is
+    /// true whenever zero is merely possible (the interval contains it), and
+    /// is the one to use before branching on "is this zero".
+    pub fn is_exact_zero(&self) -> bool {
+        self.low == 0.0 && self.high == 0.0
+    }
+
+    /// The distance from the value down to the lower bound, `v - low`.
+    pub fn lower_margin(&self) -> f32 {
+        self.v - self.low
+    }
+
+    /// The distance from the value up to the upper bound, `high - v`.  This
+    /// generally differs a little from `lower_margin()`, since the bounds are
+    /// rounded outward separately.
+    pub fn upper_margin(&self) -> f32 {
+        self.high - self.v
+    }
+
+    /// The larger of `lower_margin()` and `upper_margin()`, so that
+    /// `value() ± symmetric_error()` covers the whole interval.  This is what
+    /// `Display` prints, and is more than `absolute_error() / 2` whenever the
+    /// interval is lopsided.
+    pub fn symmetric_error(&self) -> f32 {
+        self.lower_margin().max(self.upper_margin())
+    }
+
+    /// An estimate of the relative error computed from the bounds alone (so it
+    /// is available in release builds): the larger distance from the value to
+    /// either bound, divided by the magnitude of the value.  This is infinite
+    /// when the value is zero but the interval is not.
+    pub fn relative_error_estimate(&self) -> f32 {
+        let margin = self.symmetric_error();
+        if margin == 0.0 {
+            0.0
+        } else {
+            margin / self.v.abs()
+        }
+    }
+
+    /// The largest `relative_error_estimate()` of this value or any value it
+    /// was computed from.  Comparing this against the current estimate shows
+    /// whether precision was lost at some earlier step (e.g. a cancelling
+    /// subtraction) even if later operations make the current error look
+    /// modest.
+    ///
+    /// This costs an extra `f32` per value, and every operation computes the
+    /// relative error estimates of its inputs.
+    #[cfg(feature = "peak-error")]
+    pub fn peak_relative_error(&self) -> f32 {
+        self.max_rel_seen.max(self.relative_error_estimate())
+    }
+
+    /// The summed interval widths of the constructed values this one was
+    /// computed from, i.e. the error that was already there before any
+    /// arithmetic happened.  Values used more than once are counted each time.
+    #[cfg(feature = "initial-error")]
+    pub fn initial_error(&self) -> f32 {
+        self.initial_error
+    }
+
+    /// How much of `absolute_error()` came from the computation rather than
+    /// from the inputs: `absolute_error() - initial_error()`, clamped at
+    /// zero.
+    ///
+    /// This is a budget split, not a rigorous decomposition: operations like
+    /// multiplication scale the input error too, and that scaling shows up
+    /// here as propagated error.
+    #[cfg(feature = "initial-error")]
+    pub fn propagated_error(&self) -> f32 {
+        (self.absolute_error() - self.initial_error).max(0.0)
+    }
+
+    /// The error to quote for this value: the relative error estimate scaled
+    /// back up by the magnitude of the value, which is just
+    /// `symmetric_error()`, or `absolute_error()` when the value is zero and
+    /// the relative estimate is meaningless.  Since the symmetric error only
+    /// measures the wider side of the interval, it never exceeds the
+    /// absolute error.
+    pub fn effective_error(&self) -> f32 {
+        if self.v == 0.0 {
+            self.absolute_error()
+        } else {
+            self.symmetric_error()
+        }
+    }
+
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
     pub fn relative_error(&self) -> f32 {
         ((self.precise - self.v as f64) / self.precise).abs() as f32
     }
 
-    #[cfg(debug_assertions)]
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
     pub fn precise(&self) -> f64 {
         self.precise
     }
 
+    /// Replace the precise shadow with `p`, e.g. a known-good value from a
+    /// higher precision reference, partway through a computation.  Unlike
+    /// `new_with_precise_err` this doesn't `check()`, so an oracle outside the
+    /// bounds can be reported with `check_against_precise()`; every later
+    /// operation will then fail its check.  Injecting the reference at each
+    /// step in turn finds the first one whose bounds miss it.
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+    pub fn with_precise(self, p: f64) -> EFloat32 {
+        EFloat32 { precise: p, ..self }
+    }
+
+    /// How much slack the bounds have around the precise shadow: the smaller
+    /// of `precise - low` and `high - precise`, i.e. how far the nearer bound
+    /// could move in before it would exclude the shadow.  A slack that is
+    /// large compared to `absolute_error()` means the bounds are much more
+    /// pessimistic than this particular computation needed.  Negative if the
+    /// shadow is outside the bounds.
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+    pub fn oracle_tightness(&self) -> f32 {
+        (self.precise - self.low as f64).min(self.high as f64 - self.precise) as f32
+    }
+
+    /// Whether the bounds contain the precise value.  This is the same
+    /// condition `check()` asserts, but returns the result rather than
+    /// panicking, so property tests can report failures themselves.
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+    pub fn check_against_precise(&self) -> bool {
+        self.low as f64 <= self.precise && self.precise <= self.high as f64
+    }
+
     pub fn sqrt(&self) -> EFloat32 {
         let r = EFloat32 {
             v: self.v.sqrt(),
             low: next_f32_down(self.low.sqrt()),
             high: next_f32_up(self.high.sqrt()),
-            #[cfg(debug_assertions)]
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
             precise: self.precise.sqrt(),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// `self * self`, knowing that both factors are the same value.  The
+    /// general `*` has to assume they are independent, so when the interval
+    /// straddles zero it allows a negative product; the square of a value is
+    /// never negative, so here the lower bound is zero instead.
+    pub fn sqr(&self) -> EFloat32 {
+        let (low, high) = if self.low >= 0.0 {
+            (self.low * self.low, self.high * self.high)
+        } else if self.high <= 0.0 {
+            (self.high * self.high, self.low * self.low)
+        } else {
+            (0.0, (self.low * self.low).max(self.high * self.high))
+        };
+        let r = EFloat32 {
+            v: self.v * self.v,
+            low: if low == 0.0 { 0.0 } else { next_f32_down(low) },
+            high: next_f32_up(high),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise * self.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: 2.0 * self.initial_error,
         };
         r.check();
         r
@@ -119,8 +503,12 @@ impl EFloat32 {
                 v: -self.v,
                 low: -self.high,
                 high: -self.low,
-                #[cfg(debug_assertions)]
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
                 precise: -self.precise,
+                #[cfg(feature = "peak-error")]
+                max_rel_seen: self.peak_relative_error(),
+                #[cfg(feature = "initial-error")]
+                initial_error: self.initial_error,
             };
             r.check();
             return r;
@@ -128,352 +516,5498 @@ impl EFloat32 {
             let r = EFloat32 {
                 v: self.v.abs(),
                 low: 0.0,
-                high: -self.low.max(self.high),
-                #[cfg(debug_assertions)]
+                high: (-self.low).max(self.high),
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
                 precise: self.precise.abs(),
+                #[cfg(feature = "peak-error")]
+                max_rel_seen: self.peak_relative_error(),
+                #[cfg(feature = "initial-error")]
+                initial_error: self.initial_error,
             };
             r.check();
             return r;
         }
     }
-}
 
-impl Add for EFloat32 {
-    type Output = EFloat32;
+    /// Discard the accumulated error interval, keeping only the value, when you
+    /// know from outside reasoning that the value is actually exact (e.g. it
+    /// was just snapped to a grid).
+    ///
+    /// This throws away the safety the bounds provide: if the value is not
+    /// really exact, the result's bounds are simply wrong.
+    pub fn trust_exact(self) -> EFloat32 {
+        EFloat32::new(self.v)
+    }
 
-    fn add(self, other: EFloat32) -> EFloat32 {
+    /// e^x - 1, accurate for small x where `exp(x) - 1` would cancel.
+    pub fn exp_m1(&self) -> EFloat32 {
+        self.monotone_increasing(f64::exp_m1)
+    }
+
+    pub fn ln(&self) -> EFloat32 {
+        self.monotone_increasing(f64::ln)
+    }
+
+    /// ln(1 + x), accurate for small x where `(1 + x).ln()` would lose the low
+    /// bits of x when forming `1 + x`.
+    pub fn ln_1p(&self) -> EFloat32 {
+        self.monotone_increasing(f64::ln_1p)
+    }
+
+    // Apply a monotonically increasing function to the value and both bounds.
+    // The bounds are computed in f64 and then rounded outward by one f32 ulp,
+    // which covers both the f64 error and the rounding back down to f32.
+    fn monotone_increasing(&self, f: fn(f64) -> f64) -> EFloat32 {
         let r = EFloat32 {
-            v: self.v + other.v,
-            // Interval arithemetic addition, with the result rounded away from
-            // the value r.v in order to be conservative.
-            low: next_f32_down(self.low + other.low),
-            high: next_f32_up(self.high + other.high),
-            #[cfg(debug_assertions)]
-            precise: self.precise + other.precise,
+            v: f(self.v as f64) as f32,
+            low: next_f32_down(f(self.low as f64) as f32),
+            high: next_f32_up(f(self.high as f64) as f32),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: f(self.precise),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
         };
         r.check();
         r
     }
-}
-
-impl Sub for EFloat32 {
-    type Output = EFloat32;
 
-    fn sub(self, other: EFloat32) -> EFloat32 {
+    // As monotone_increasing, but for a decreasing function, so the bounds
+    // swap places.
+    fn monotone_decreasing(&self, f: fn(f64) -> f64) -> EFloat32 {
         let r = EFloat32 {
-            v: self.v - other.v,
-            low: next_f32_down(self.low - other.high),
-            high: next_f32_up(self.high - other.low),
-            #[cfg(debug_assertions)]
-            precise: self.precise - other.precise,
+            v: f(self.v as f64) as f32,
+            low: next_f32_down(f(self.high as f64) as f32),
+            high: next_f32_up(f(self.low as f64) as f32),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: f(self.precise),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
         };
         r.check();
         r
     }
-}
-
-impl Mul for EFloat32 {
-    type Output = EFloat32;
-
-    fn mul(self, other: EFloat32) -> EFloat32 {
-        let prod: [f32; 4] = [
-            self.low * other.low,
-            self.high * other.low,
-            self.low * other.high,
-            self.high * other.high,
-        ];
 
+    /// Apply a function the crate doesn't provide, which the caller
+    /// guarantees is monotonically increasing over the whole interval.  It is
+    /// applied to both bounds, which are then rounded outward by one ulp, so
+    /// `f` should also be accurate to within an ulp or so (as `f32` library
+    /// functions usually are).  Neither property is checked, and if either
+    /// fails the bounds can be wrong.
+    ///
+    /// With precise tracking, the shadow becomes `f` of the shadow rounded to
+    /// `f32`, so it is only as precise as `f` itself from here on.
+    pub fn map_monotone_increasing(&self, f: impl Fn(f32) -> f32) -> EFloat32 {
         let r = EFloat32 {
-            v: self.v * other.v,
-            low: next_f32_down(prod[0].min(prod[1]).min(prod[2].min(prod[3]))),
-            high: next_f32_up(prod[0].max(prod[1]).max(prod[2].max(prod[3]))),
-            #[cfg(debug_assertions)]
-            precise: self.precise * other.precise,
+            v: f(self.v),
+            low: next_f32_down(f(self.low)),
+            high: next_f32_up(f(self.high)),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: f(self.precise as f32) as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
         };
         r.check();
         r
     }
-}
-
-impl Div for EFloat32 {
-    type Output = EFloat32;
-
-    fn div(self, other: EFloat32) -> EFloat32 {
-        if other.low < 0.0 && other.high > 0.0 {
-            // Bah. the interval we are dividing straddles zero, so just
-            // return an interval of everything.
-            return EFloat32 {
-                v: self.v / other.v,
-                low: -::std::f32::INFINITY,
-                high: ::std::f32::INFINITY,
-                #[cfg(debug_assertions)]
-                precise: self.precise / other.precise,
-            };
-        }
-        let prod: [f32; 4] = [
-            self.low / other.low,
-            self.high / other.low,
-            self.low / other.high,
-            self.high / other.high,
-        ];
 
+    /// As `map_monotone_increasing`, for a function the caller guarantees is
+    /// monotonically decreasing, so the bounds swap places.
+    pub fn map_monotone_decreasing(&self, f: impl Fn(f32) -> f32) -> EFloat32 {
         let r = EFloat32 {
-            v: self.v / other.v,
-            low: next_f32_down(prod[0].min(prod[1]).min(prod[2].min(prod[3]))),
-            high: next_f32_up(prod[0].max(prod[1]).max(prod[2].max(prod[3]))),
-            #[cfg(debug_assertions)]
-            precise: self.precise / other.precise,
+            v: f(self.v),
+            low: next_f32_down(f(self.high)),
+            high: next_f32_up(f(self.low)),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: f(self.precise as f32) as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
         };
         r.check();
         r
     }
-}
 
-impl Rem for EFloat32 {
-    type Output = EFloat32;
+    // Apply a non-decreasing function whose result is exact on f32s (floor,
+    // ceil, etc.), so the bounds need no rounding outward.
+    fn monotone_exact(&self, f: fn(f64) -> f64) -> EFloat32 {
+        let r = EFloat32 {
+            v: f(self.v as f64) as f32,
+            low: f(self.low as f64) as f32,
+            high: f(self.high as f64) as f32,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: f(self.precise),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
 
-    fn rem(self, other: EFloat32) -> EFloat32 {
-        if other.low < 0.0 && other.high > 0.0 {
-            // Bah. the interval we are dividing straddles zero, so just
-            // return an interval of everything.
-            return EFloat32 {
-                v: self.v / other.v,
-                low: -::std::f32::INFINITY,
-                high: ::std::f32::INFINITY,
-                #[cfg(debug_assertions)]
-                precise: self.precise / other.precise,
+    // Bounds for sin or cos (passed as `f`), whose maximum of 1.0 is at
+    // `peak` (mod 2*pi) and minimum of -1.0 half a period later.  Within the
+    // interval the extremes are either at the endpoints or at one of those
+    // turning points.
+    fn periodic(&self, f: fn(f64) -> f64, peak: f64) -> EFloat32 {
+        let (lo, hi) = (self.low as f64, self.high as f64);
+        let (low, high) = if !lo.is_finite() || !hi.is_finite() || hi - lo >= 2.0 * PI {
+            (-1.0, 1.0)
+        } else {
+            let contains = |point: f64| {
+                let k = ((lo - point) / (2.0 * PI)).ceil();
+                point + k * 2.0 * PI <= hi
             };
-        }
-        let prod: [f32; 4] = [
-            self.low % other.low,
-            self.high % other.low,
-            self.low % other.high,
-            self.high % other.high,
-        ];
-
+            let (fl, fh) = (f(lo), f(hi));
+            let low = if contains(peak + PI) {
+                -1.0
+            } else {
+                next_f32_down(fl.min(fh) as f32).max(-1.0)
+            };
+            let high = if contains(peak) {
+                1.0
+            } else {
+                next_f32_up(fl.max(fh) as f32).min(1.0)
+            };
+            (low, high)
+        };
         let r = EFloat32 {
-            v: self.v % other.v,
-            low: next_f32_down(prod[0].min(prod[1]).min(prod[2].min(prod[3]))),
-            high: next_f32_up(prod[0].max(prod[1]).max(prod[2].max(prod[3]))),
-            #[cfg(debug_assertions)]
-            precise: self.precise / other.precise,
+            v: f(self.v as f64) as f32,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: f(self.precise),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
         };
         r.check();
         r
     }
-}
 
-impl Neg for EFloat32 {
-    type Output = EFloat32;
+    pub fn sinh(&self) -> EFloat32 {
+        self.monotone_increasing(f64::sinh)
+    }
 
-    fn neg(self) -> EFloat32 {
+    /// cosh has its minimum of exactly 1.0 at zero, so when the interval
+    /// straddles zero the lower bound is 1.0 rather than either endpoint.
+    pub fn cosh(&self) -> EFloat32 {
+        let cosh_low = (self.low as f64).cosh();
+        let cosh_high = (self.high as f64).cosh();
+        let (low, high) = if self.low >= 0.0 {
+            // increasing over the whole interval
+            (
+                next_f32_down(cosh_low as f32),
+                next_f32_up(cosh_high as f32),
+            )
+        } else if self.high <= 0.0 {
+            // decreasing over the whole interval
+            (
+                next_f32_down(cosh_high as f32),
+                next_f32_up(cosh_low as f32),
+            )
+        } else {
+            (1.0, next_f32_up(cosh_low.max(cosh_high) as f32))
+        };
         let r = EFloat32 {
-            v: -self.v,
-            low: -self.high,
-            high: -self.low,
-            #[cfg(debug_assertions)]
-            precise: -self.precise,
+            v: self.v.cosh(),
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise.cosh(),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
         };
         r.check();
         r
     }
-}
 
-impl PartialEq for EFloat32 {
-    fn eq(&self, other: &EFloat32) -> bool {
-        self.v == other.v
+    pub fn tanh(&self) -> EFloat32 {
+        self.monotone_increasing(f64::tanh)
     }
-}
 
-impl PartialOrd for EFloat32 {
-    fn partial_cmp(&self, other: &EFloat32) -> Option<Ordering> {
-        self.v.partial_cmp(&other.v)
+    /// The positive difference, `max(self - other, 0)`.
+    pub fn abs_sub(self, other: EFloat32) -> EFloat32 {
+        let d = self - other;
+        if d.low >= 0.0 {
+            // definitely positive, so this is plain subtraction
+            d
+        } else if d.high <= 0.0 {
+            EFloat32::zero()
+        } else {
+            // the difference straddles zero, so clamp the low bound
+            let r = EFloat32 {
+                v: d.v.max(0.0),
+                low: 0.0,
+                high: d.high,
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+                precise: d.precise.max(0.0),
+                #[cfg(feature = "peak-error")]
+                max_rel_seen: d.peak_relative_error(),
+                #[cfg(feature = "initial-error")]
+                initial_error: d.initial_error,
+            };
+            r.check();
+            r
+        }
     }
-}
 
-impl Zero for EFloat32 {
-    fn zero() -> EFloat32 {
-        EFloat32 {
-            v: 0.0,
-            low: 0.0,
-            high: 0.0,
-            #[cfg(debug_assertions)]
-            precise: 0.0,
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            value: self.v,
+            low: self.low,
+            high: self.high,
+            absolute_error: self.absolute_error(),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: Some(self.precise),
+            #[cfg(not(any(debug_assertions, feature = "precise-tracking")))]
+            precise: None,
         }
     }
 
-    fn is_zero(&self) -> bool {
-        self.low <= 0.0 && self.high >= 0.0
+    /// Multiply after scaling both operands by powers of two so their largest
+    /// bound is near 1.0, then scale the product back.  Scaling by a power of
+    /// two is exact, so this introduces no rounding of its own; the only
+    /// rounding is the product itself, plus an outward step if scaling back
+    /// into the subnormal range (or past `f32::MAX`) turns out to be inexact.
+    ///
+    /// In practice the default `*` already gives the same bounds whenever the
+    /// product is a normal float (IEEE multiplication has the same relative
+    /// rounding error at any magnitude, so `1e20 * 1e-20` is just as tight
+    /// either way).  That is why this is a separate method rather than a
+    /// replacement for `Mul`.  If an operand cannot be scaled exactly (its
+    /// bounds are too far apart in magnitude) this falls back to `*`.
+    pub fn mul_scaled(self, other: EFloat32) -> EFloat32 {
+        let ea = self.scale_exponent();
+        let eb = other.scale_exponent();
+        let (sa, sb) = match (self.scaled_by(-ea), other.scaled_by(-eb)) {
+            (Some(sa), Some(sb)) => (sa, sb),
+            _ => return self * other,
+        };
+        let p = sa * sb;
+        let e = ea + eb;
+        let r = EFloat32 {
+            v: (p.v as f64 * 2f64.powi(e)) as f32,
+            low: f64_to_f32_down(p.low as f64 * 2f64.powi(e)),
+            high: f64_to_f32_up(p.high as f64 * 2f64.powi(e)),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: p.precise * 2f64.powi(e),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: p.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: p.initial_error,
+        };
+        r.check();
+        r
     }
-}
 
-impl One for EFloat32 {
-    fn one() -> EFloat32 {
-        EFloat32 {
-            v: 1.0,
-            low: 1.0,
-            high: 1.0,
-            #[cfg(debug_assertions)]
-            precise: 1.0,
+    // The binary exponent of the larger-magnitude bound, or 0 if that is not
+    // a normal float.
+    fn scale_exponent(&self) -> i32 {
+        let m = self.low.abs().max(self.high.abs());
+        if m.is_normal() {
+            ((f32_to_bits(m) >> 23) & 0xff) as i32 - 127
+        } else {
+            0
         }
     }
 
-    fn is_one(&self) -> bool {
-        self.low <= 1.0 && self.high >= 1.0
-    }
-}
-
-impl Num for EFloat32 {
-    type FromStrRadixErr = ParseFloatError;
-
-    fn from_str_radix(src: &str, radix: u32) -> Result<EFloat32, ParseFloatError> {
-        let f = f32::from_str_radix(src, radix)?;
-        Ok(EFloat32 {
-            v: f,
-            low: f,
-            high: f,
-            #[cfg(debug_assertions)]
-            precise: f as f64,
+    // Multiply the value and bounds by 2^e, or None if any of them would be
+    // rounded in the process.
+    fn scaled_by(&self, e: i32) -> Option<EFloat32> {
+        let scale = 2f64.powi(e);
+        let exact = |x: f32| {
+            let y = x as f64 * scale;
+            if (y as f32) as f64 == y {
+                Some(y as f32)
+            } else {
+                None
+            }
+        };
+        Some(EFloat32 {
+            v: exact(self.v)?,
+            low: exact(self.low)?,
+            high: exact(self.high)?,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise * scale,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
         })
     }
-}
 
-impl ToPrimitive for EFloat32 {
-    fn to_i64(&self) -> Option<i64> {
-        self.v.to_i64()
+    /// The value and its bounds, as `(value, lower_bound, upper_bound)`.
+    pub fn into_parts(self) -> (f32, f32, f32) {
+        (self.v, self.low, self.high)
     }
 
-    fn to_u64(&self) -> Option<u64> {
-        self.v.to_u64()
+    /// Rebuild an `EFloat32` from a value and its bounds, as returned by
+    /// `into_parts()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `low <= v <= high` (which also rejects NaNs).
+    pub fn from_parts(v: f32, low: f32, high: f32) -> EFloat32 {
+        assert!(
+            low <= v && v <= high,
+            "EFloat32 parts out of order: {} <= {} <= {}",
+            low,
+            v,
+            high
+        );
+        let ef = EFloat32 {
+            v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: v as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: high - low,
+        };
+        ef.check();
+        ef
     }
 
-    fn to_isize(&self) -> Option<isize> {
-        self.v.to_isize()
+    // As from_parts, but without the ordering assert or check(), for parts
+    // that came from an unchecked computation and may be NaN.
+    pub(crate) fn from_parts_unchecked(v: f32, low: f32, high: f32) -> EFloat32 {
+        EFloat32 {
+            v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: v as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: high - low,
+        }
+    }
+
+    /// Add, returning `None` if the result's interval reaches infinity even
+    /// though both inputs were finite.  This catches the step at which an
+    /// accumulation overflows, so you can switch to another strategy.
+    pub fn checked_add(self, other: EFloat32) -> Option<EFloat32> {
+        let inputs_finite = self.low.is_finite()
+            && self.high.is_finite()
+            && other.low.is_finite()
+            && other.high.is_finite();
+        let r = self + other;
+        if inputs_finite && (r.low.is_infinite() || r.high.is_infinite()) {
+            None
+        } else {
+            Some(r)
+        }
+    }
+
+    pub fn recip(&self) -> EFloat32 {
+        if self.low <= 0.0 && self.high >= 0.0 {
+            // The interval includes zero, whose reciprocal is unbounded, so
+            // just return an interval of everything (as Div does).
+            let r = EFloat32 {
+                v: self.v.recip(),
+                low: -f32::INFINITY,
+                high: f32::INFINITY,
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+                precise: self.precise.recip(),
+                #[cfg(feature = "peak-error")]
+                max_rel_seen: self.peak_relative_error(),
+                #[cfg(feature = "initial-error")]
+                initial_error: self.initial_error,
+            };
+            #[cfg(feature = "infinity-events")]
+            note_infinity(&[*self], &r);
+            #[cfg(feature = "log")]
+            warn_if_inaccurate("recip", &r);
+            return r;
+        }
+        // 1/x is decreasing on either side of zero
+        let r = EFloat32 {
+            v: self.v.recip(),
+            low: next_f32_down(self.high.recip()),
+            high: next_f32_up(self.low.recip()),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise.recip(),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        #[cfg(feature = "infinity-events")]
+        note_infinity(&[*self], &r);
+        #[cfg(feature = "log")]
+        warn_if_inaccurate("recip", &r);
+        r
+    }
+
+    /// Tighten the bounds so that neither is more than `|value| * max_rel` away
+    /// from the value.  Bounds that are already tighter are left alone, so
+    /// this never widens the interval.
+    ///
+    /// This is a way to override pessimistic bounds with outside knowledge,
+    /// and it is unsound if that knowledge is wrong.  With precise tracking a
+    /// cap that excludes the precise value will fail `check()`.
+    pub fn cap_relative_error(&self, max_rel: f32) -> EFloat32 {
+        let cap = self.v.abs() * max_rel;
+        let r = EFloat32 {
+            v: self.v,
+            low: self.low.max(next_f32_down(self.v - cap)),
+            high: self.high.min(next_f32_up(self.v + cap)),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// Whether the two intervals share any point, i.e. whether the two values
+    /// might actually be equal.  Unlike `==`, which only compares the values,
+    /// this takes the error bounds into account.
+    pub fn overlaps(&self, other: &EFloat32) -> bool {
+        self.low <= other.high && other.low <= self.high
+    }
+
+    /// The order of the two values if it is certain, i.e. if the intervals
+    /// are disjoint, and `None` whenever `overlaps` is true, including for
+    /// intervals that only touch and for equal exact values.  Swapping only
+    /// on `Some` lets a sort leave ambiguous pairs in input order.
+    pub fn compare_definite(&self, other: &EFloat32) -> Option<Ordering> {
+        if self.high < other.low {
+            Some(Ordering::Less)
+        } else if other.high < self.low {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+
+    /// Whether every value in the interval is less than `x`.  Unlike `< x`,
+    /// which only compares the value, this holds only if the true value is
+    /// known to be less.  NaN bounds make every `definitely_*` test false.
+    pub fn definitely_lt(&self, x: f32) -> bool {
+        self.high < x
+    }
+
+    /// Whether every value in the interval is at most `x`.
+    pub fn definitely_le(&self, x: f32) -> bool {
+        self.high <= x
+    }
+
+    /// Whether every value in the interval is greater than `x`.
+    pub fn definitely_gt(&self, x: f32) -> bool {
+        self.low > x
+    }
+
+    /// Whether every value in the interval is at least `x`.
+    pub fn definitely_ge(&self, x: f32) -> bool {
+        self.low >= x
+    }
+
+    /// Widen both bounds by `extra_abs`, to account for error introduced
+    /// outside of this crate (e.g. by a function with a known error contract).
+    pub fn add_error(&self, extra_abs: f32) -> EFloat32 {
+        let r = EFloat32 {
+            v: self.v,
+            low: next_f32_down(self.low - extra_abs),
+            high: next_f32_up(self.high + extra_abs),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// Widen both bounds by `rel * |value|`.
+    pub fn add_error_relative(&self, rel: f32) -> EFloat32 {
+        self.add_error(next_f32_up(rel * self.v.abs()))
+    }
+
+    /// Subtract, also returning whether catastrophic cancellation occurred:
+    /// `true` when the width of the result's interval exceeds the magnitude of
+    /// its value, so not even the leading digit can be trusted.
+    pub fn sub_checked(self, other: EFloat32) -> (EFloat32, bool) {
+        let r = self - other;
+        let cancelled = r.absolute_error() > r.v.abs();
+        (r, cancelled)
+    }
+
+    /// The floor of the value, if it is unambiguous: `Some` only when both
+    /// bounds have the same floor (and it fits in an `i32`).  `Float::floor`
+    /// instead widens to cover both candidate integers without saying so.
+    pub fn floor_exact(&self) -> Option<i32> {
+        let floor = self.low.floor();
+        if floor == self.high.floor() && floor >= i32::MIN as f32 && floor < i32::MAX as f32 {
+            Some(floor as i32)
+        } else {
+            None
+        }
+    }
+
+    /// The width of the interval as a percentage of the magnitude of the
+    /// value, for reports.  At a value of zero this is infinite, unless the
+    /// value is exactly zero (with no error), in which case it is `0.0`.
+    pub fn error_percent(&self) -> f32 {
+        let err = self.absolute_error();
+        if self.v == 0.0 {
+            if err == 0.0 {
+                0.0
+            } else {
+                f32::INFINITY
+            }
+        } else {
+            100.0 * err / self.v.abs()
+        }
+    }
+
+    /// The logarithm of `self` in base `base`, computed as
+    /// `self.ln() / base.ln()`.
+    ///
+    /// Both operands must be positive.  If either interval includes
+    /// non-positive values the result is NaN (for a definitely non-positive
+    /// operand) or an interval of everything (when it merely straddles zero).
+    /// A base straddling 1.0 also gives an interval of everything, since its
+    /// logarithm straddles zero.
+    pub fn log(&self, base: EFloat32) -> EFloat32 {
+        if self.high <= 0.0 || base.high <= 0.0 {
+            return EFloat32::nan();
+        }
+        if self.low <= 0.0 || base.low <= 0.0 {
+            return EFloat32 {
+                v: self.v.log(base.v),
+                low: f32::NEG_INFINITY,
+                high: f32::INFINITY,
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+                precise: self.precise.log(base.precise),
+                #[cfg(feature = "peak-error")]
+                max_rel_seen: self.peak_relative_error().max(base.peak_relative_error()),
+                #[cfg(feature = "initial-error")]
+                initial_error: self.initial_error + base.initial_error,
+            };
+        }
+        self.ln() / base.ln()
+    }
+
+    /// Divide, but report an error instead of returning an infinite interval
+    /// when the divisor includes zero, or when the result is otherwise not
+    /// finite.
+    pub fn try_div(self, other: EFloat32) -> Result<EFloat32, EFloatError> {
+        other.check_divisor()?;
+        (self / other).finite_or_err()
+    }
+
+    /// Divide, keeping the bounds tight when the operands' exponents are far
+    /// apart.  The quotient of two `f32`s always fits comfortably in `f64`'s
+    /// exponent range, so dividing in `f64` does the job of scaling both
+    /// operands into a comparable range and back, and the bounds are rounded
+    /// to `f32` just once, in the outward direction.  That gives bounds at
+    /// most one ulp wide for exact operands (the plain `/` widens by an ulp
+    /// on each side), and the tightest possible enclosure when the quotient
+    /// is subnormal or overflows.  The value is the same as `/` gives.
+    ///
+    /// A divisor that includes zero, or non-finite bounds, fall back to `/`.
+    pub fn div_scaled(self, other: EFloat32) -> EFloat32 {
+        let bounds = [self.low, self.high, other.low, other.high];
+        if (other.low <= 0.0 && other.high >= 0.0) || bounds.iter().any(|x| !x.is_finite()) {
+            return self / other;
+        }
+        let quotients = [
+            self.low as f64 / other.low as f64,
+            self.high as f64 / other.low as f64,
+            self.low as f64 / other.high as f64,
+            self.high as f64 / other.high as f64,
+        ];
+        let low = quotients.iter().cloned().fold(f64::INFINITY, f64::min);
+        let high = quotients.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let r = EFloat32 {
+            v: self.v / other.v,
+            low: f64_to_f32_down(next_f64_down(low)),
+            high: f64_to_f32_up(next_f64_up(high)),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise / other.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + other.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// Multiply by `2^exp`, which only changes the exponent, so unlike `*`
+    /// it is exact and leaves the bounds exactly as many ulps wide as they
+    /// were.  If the result overflows, or becomes subnormal and loses bits,
+    /// the bounds are rounded outward as usual.
+    pub fn scale_pow2(&self, exp: i32) -> EFloat32 {
+        // Scaling the largest f32 down or the smallest up by 2^400 already
+        // saturates, and keeps the f64 factor finite and nonzero.
+        let factor = 2f64.powi(exp.clamp(-400, 400));
+        let (low, high) = (self.low as f64 * factor, self.high as f64 * factor);
+        let r = EFloat32 {
+            v: (self.v as f64 * factor) as f32,
+            low: f64_to_f32_down(low),
+            high: f64_to_f32_up(high),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise * factor,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// Multiply by exactly two, as `scale_pow2(1)`.  Handy when bisecting.
+    pub fn double(&self) -> EFloat32 {
+        self.scale_pow2(1)
+    }
+
+    /// Divide by exactly two, as `scale_pow2(-1)`.  Handy for midpoints and
+    /// averages.
+    pub fn halve(&self) -> EFloat32 {
+        self.scale_pow2(-1)
+    }
+
+    /// The remainder, with the same checks as `try_div`.
+    pub fn try_rem(self, other: EFloat32) -> Result<EFloat32, EFloatError> {
+        other.check_divisor()?;
+        (self % other).finite_or_err()
+    }
+
+    fn check_divisor(&self) -> Result<(), EFloatError> {
+        if self.low == 0.0 && self.high == 0.0 {
+            Err(EFloatError::DivisorIsZero)
+        } else if self.low <= 0.0 && self.high >= 0.0 {
+            Err(EFloatError::DivisorStraddlesZero)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn finite_or_err(self) -> Result<EFloat32, EFloatError> {
+        if self.low.is_finite() && self.high.is_finite() {
+            Ok(self)
+        } else {
+            Err(EFloatError::NonFinite)
+        }
+    }
+
+    /// If the value is known to be an integer and the interval contains
+    /// exactly one integer, that integer must be the value, so return it as
+    /// an exact `EFloat32`.  Returns `None` if the interval contains no
+    /// integers or more than one.
+    pub fn snap_to_integer(&self) -> Option<EFloat32> {
+        let candidate = self.low.ceil();
+        if candidate == self.high.floor() {
+            Some(EFloat32::new(candidate))
+        } else {
+            None
+        }
+    }
+
+    /// Format the value along with its error, in the given style.
+    pub fn format_with(&self, style: ErrorStyle) -> String {
+        match style {
+            ErrorStyle::PlusMinus => format!("{} ± {}", self.v, self.symmetric_error()),
+            ErrorStyle::Interval => format!("[{}, {}]", self.low, self.high),
+            ErrorStyle::Ulps => {
+                let ulps = ulps_between(self.low, self.v).max(ulps_between(self.v, self.high));
+                format!("{} ({} ulp)", self.v, ulps)
+            }
+            ErrorStyle::Percent => {
+                format!("{} ({}%)", self.v, 100.0 * self.relative_error_estimate())
+            }
+        }
+    }
+
+    /// A human readable summary for trace logs, e.g.
+    /// `0.205 (±1.2e-7, ~20 significant bits)`: the value, the larger
+    /// distance to either bound (as in `Display`), and `significant_bits()`.
+    /// The wording may change, so don't parse it; use `Display` or
+    /// `format_with` for that.
+    pub fn describe(&self) -> String {
+        format!(
+            "{} (±{:.1e}, ~{} significant bits)",
+            self.v,
+            self.symmetric_error(),
+            self.significant_bits()
+        )
+    }
+
+    /// A total ordering on the values, as `f32::total_cmp`, for sorting.
+    /// Unlike `partial_cmp` this orders NaN values (after positive infinity,
+    /// or before negative infinity if negative) instead of giving up.  The
+    /// bounds are not considered.
+    pub fn total_cmp(&self, other: &EFloat32) -> Ordering {
+        self.v.total_cmp(&other.v)
+    }
+
+    /// Whether the bounds have become meaningless: either is NaN, or they are
+    /// out of order.
+    pub fn is_degenerate(&self) -> bool {
+        self.low.is_nan() || self.high.is_nan() || self.low > self.high
+    }
+
+    /// A defensive recovery for degenerate intervals: bounds that are out of
+    /// order are swapped, and a NaN lower (upper) bound is replaced by
+    /// negative (positive) infinity.  A healthy interval is returned as is.
+    pub fn repaired(&self) -> EFloat32 {
+        let (mut low, mut high) = if self.low > self.high {
+            (self.high, self.low)
+        } else {
+            (self.low, self.high)
+        };
+        if low.is_nan() {
+            low = f32::NEG_INFINITY;
+        }
+        if high.is_nan() {
+            high = f32::INFINITY;
+        }
+        EFloat32 {
+            v: self.v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.max_rel_seen,
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        }
+    }
+
+    /// The number of distinct `f32`s in `[low, high]`, counting `0.0` and
+    /// `-0.0` as one, so an exact value has a count of 1 and an interval one
+    /// ulp wide has 2.  This is the finest possible measure of the width.
+    /// Degenerate (NaN or out of order) bounds have a count of 0.
+    pub fn representable_count(&self) -> u64 {
+        if self.is_degenerate() {
+            0
+        } else {
+            ulps_between(self.low, self.high) + 1
+        }
+    }
+
+    /// Whether the value is good to `digits` significant decimal digits,
+    /// i.e. `relative_error_estimate() < 10^-digits`.  An exact value always
+    /// is, and an interval that straddles zero never is (unless exact), since
+    /// not even the sign is known.
+    pub fn is_accurate_to_decimals(&self, digits: u32) -> bool {
+        if self.is_exact() {
+            true
+        } else if self.low <= 0.0 && self.high >= 0.0 {
+            false
+        } else {
+            (self.relative_error_estimate() as f64) < 10f64.powi(-(digits as i32))
+        }
+    }
+
+    /// Roughly how many leading bits of the value can be trusted:
+    /// `-log2(relative_error_estimate())`, rounded down and clamped to
+    /// `[0, 24]`.  An exact value has all 24 bits, and an interval that
+    /// straddles zero has none, since not even the sign is known.
+    pub fn significant_bits(&self) -> u32 {
+        let rel = self.relative_error_estimate();
+        if rel == 0.0 {
+            24
+        } else if self.low <= 0.0 && self.high >= 0.0 {
+            0
+        } else {
+            (-rel.log2()).floor().clamp(0.0, 24.0) as u32
+        }
+    }
+
+    /// The remainder with C `fmod` semantics: `self - trunc(self / other) *
+    /// other`, which takes the sign of the dividend.  (This is also what `%`
+    /// does on `f32`, but the bounds here are computed differently.)
+    ///
+    /// If the truncated quotient is the same integer everywhere in the two
+    /// intervals, the remainder is monotone in each operand and the bounds
+    /// are the (exact) remainders at the corners.  Otherwise the dividend
+    /// crosses a multiple of the divisor, and all that is known is that the
+    /// result is no larger in magnitude than either the dividend or the
+    /// divisor, with the dividend's sign.  A divisor that includes zero gives
+    /// an interval of everything.
+    pub fn fmod(&self, other: &EFloat32) -> EFloat32 {
+        let v = self.v % other.v;
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        let precise = self.precise % other.precise;
+        #[cfg(feature = "peak-error")]
+        let max_rel_seen = self.peak_relative_error().max(other.peak_relative_error());
+        #[cfg(feature = "initial-error")]
+        let initial_error = self.initial_error + other.initial_error;
+
+        if other.low <= 0.0 && other.high >= 0.0 {
+            return EFloat32 {
+                v,
+                low: f32::NEG_INFINITY,
+                high: f32::INFINITY,
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+                precise,
+                #[cfg(feature = "peak-error")]
+                max_rel_seen,
+                #[cfg(feature = "initial-error")]
+                initial_error,
+            };
+        }
+
+        let corners = [
+            (self.low, other.low),
+            (self.high, other.low),
+            (self.low, other.high),
+            (self.high, other.high),
+        ];
+        // The f64 quotient of two f32s truncates correctly as long as it is
+        // below 2^24; beyond that, the remainder is effectively unknown anyway.
+        let quotient = |(x, y): (f32, f32)| (x as f64 / y as f64).trunc();
+        let q = quotient(corners[0]);
+        let same_quotient = q.abs() < 16777216.0 && corners.iter().all(|&c| quotient(c) == q);
+
+        let (low, high) = if same_quotient {
+            let mut low = f32::INFINITY;
+            let mut high = f32::NEG_INFINITY;
+            for &(x, y) in corners.iter() {
+                low = low.min(x % y);
+                high = high.max(x % y);
+            }
+            (low, high)
+        } else {
+            let divisor = other.low.abs().max(other.high.abs());
+            (
+                self.low.max(-divisor).min(0.0),
+                self.high.min(divisor).max(0.0),
+            )
+        };
+        let r = EFloat32 {
+            v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen,
+            #[cfg(feature = "initial-error")]
+            initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// The bounds as half precision floats, rounded outward so that the f16
+    /// interval still contains the f32 one.  Since f16 has only 11 bits of
+    /// precision this widens the interval, by up to an f16 ulp on each side.
+    #[cfg(feature = "half")]
+    pub fn to_f16_interval(&self) -> (f16, f16) {
+        (f32_to_f16_down(self.low), f32_to_f16_up(self.high))
+    }
+
+    /// The bounds as a `RangeInclusive`, for use with `contains()` and other
+    /// APIs that take ranges.
+    pub fn as_range(&self) -> RangeInclusive<f32> {
+        self.low..=self.high
+    }
+
+    /// Convert an integer, but only if it is exactly representable as an
+    /// `f32` (every integer with magnitude up to 2^24 is, and beyond that
+    /// only multiples of ever larger powers of two).  Returns `None`
+    /// otherwise, where `NumCast::from` would silently round.
+    pub fn from_i64_checked(n: i64) -> Option<EFloat32> {
+        let f = n as f32;
+        if f as i128 == n as i128 {
+            Some(EFloat32::new(f))
+        } else {
+            None
+        }
+    }
+
+    /// Convert an integer.  If it is not exactly representable as an `f32`,
+    /// the value is the nearest `f32` and the bounds are the two `f32`s on
+    /// either side of the integer.
+    pub fn from_i64(n: i64) -> EFloat32 {
+        let f = n as f32;
+        let (low, high) = match (f as i128).cmp(&(n as i128)) {
+            Ordering::Equal => (f, f),
+            Ordering::Greater => (next_f32_down(f), f),
+            Ordering::Less => (f, next_f32_up(f)),
+        };
+        let ef = EFloat32 {
+            v: f,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: n as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: high - low,
+        };
+        ef.check();
+        ef
+    }
+
+    /// Subtract, given a hint of how correlated the errors of the operands
+    /// are, from `0.0` (independent, the same as `-`) to `1.0` (the same
+    /// error in both, as when `other` is `self` or a copy of it shifted by an
+    /// exact amount).
+    ///
+    /// Interval arithmetic otherwise has to assume the operands are
+    /// independent, so `x - x` gives an interval twice as wide as `x` rather
+    /// than zero.  With full correlation the errors cancel and only the
+    /// rounding of the difference of the values remains; in between, the
+    /// bounds are interpolated.  The hint is trusted: with precise tracking a
+    /// wrong hint will fail the bounds check.
+    ///
+    /// Panics if `correlation` is not within `[0.0, 1.0]`.
+    pub fn sub_correlated(self, other: EFloat32, correlation: f32) -> EFloat32 {
+        assert!(
+            (0.0..=1.0).contains(&correlation),
+            "correlation must be within [0, 1]: {}",
+            correlation
+        );
+        let independent = self - other;
+        if correlation == 0.0 {
+            return independent;
+        }
+        let (d, err) = two_sum(self.v as f64, -(other.v as f64));
+        let (corr_low, corr_high) = if err == 0.0 {
+            (f64_to_f32_down(d), f64_to_f32_up(d))
+        } else {
+            (
+                f64_to_f32_down(next_f64_down(d)),
+                f64_to_f32_up(next_f64_up(d)),
+            )
+        };
+        let w = 1.0 - correlation;
+        let (low, high) = if w == 0.0 {
+            (corr_low, corr_high)
+        } else {
+            (
+                next_f32_down(corr_low - w * (corr_low - independent.low)),
+                next_f32_up(corr_high + w * (independent.high - corr_high)),
+            )
+        };
+        let r = EFloat32 {
+            v: independent.v,
+            low: low.max(independent.low),
+            high: high.min(independent.high),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: independent.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: independent.max_rel_seen,
+            #[cfg(feature = "initial-error")]
+            initial_error: independent.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// The value and its bounds as `[value, lower_bound, upper_bound]`, a
+    /// fixed-size encoding for binary formats and FFI.
+    pub fn to_array(&self) -> [f32; 3] {
+        [self.v, self.low, self.high]
+    }
+
+    /// Rebuild an `EFloat32` from the array returned by `to_array()`.
+    ///
+    /// # Panics
+    ///
+    /// As `from_parts`, panics unless `low <= v <= high`.
+    pub fn from_array(a: [f32; 3]) -> EFloat32 {
+        EFloat32::from_parts(a[0], a[1], a[2])
+    }
+
+    /// The `n`th root.  Square and cube roots use `sqrt` and `cbrt`, other
+    /// roots `powf(1/n)`, all computed in f64 where the error from rounding
+    /// `1/n` is far below an f32 ulp.
+    ///
+    /// Odd roots are defined (and increasing) everywhere, so negative values
+    /// are fine.  Even roots of an interval that is entirely negative are
+    /// NaN; if the interval merely straddles zero, the result is the root of
+    /// its non-negative part, starting at zero, and a negative value becomes
+    /// zero.  The zeroth root is NaN.
+    pub fn nth_root(&self, n: u32) -> EFloat32 {
+        // is_multiple_of would need Rust 1.87
+        #[allow(clippy::manual_is_multiple_of)]
+        let even = n % 2 == 0;
+        if n == 0 || (even && self.high < 0.0) {
+            return EFloat32::nan();
+        }
+        let root = |x: f64| match n {
+            1 => x,
+            2 => x.sqrt(),
+            3 => x.cbrt(),
+            _ => x.signum() * x.abs().powf(1.0 / n as f64),
+        };
+        // Even roots only see the non-negative part, as in clamp_nonnegative.
+        let domain = |x: f64| if even { x.max(0.0) } else { x };
+        let r = EFloat32 {
+            v: root(domain(self.v as f64)) as f32,
+            low: if even && self.low < 0.0 {
+                0.0
+            } else {
+                next_f32_down(root(self.low as f64) as f32)
+            },
+            high: next_f32_up(root(self.high as f64) as f32),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: root(domain(self.precise)),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// Clamp to the non-negative part of the interval, for quantities (areas,
+    /// distances, probabilities) that are known not to be negative but may
+    /// have dipped below zero through rounding.  An interval that is entirely
+    /// negative collapses to an exact zero.
+    pub fn clamp_nonnegative(&self) -> EFloat32 {
+        if self.high < 0.0 {
+            return EFloat32::zero();
+        }
+        let r = EFloat32 {
+            v: self.v.max(0.0),
+            low: self.low.max(0.0),
+            high: self.high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise.max(0.0),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.max_rel_seen,
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// `self * other`, saturated into `[lo, hi]`, for quantities with a
+    /// fixed range like color channels in `[0, 1]`.  The value and both
+    /// bounds are clamped, so a product that is entirely above `hi` becomes
+    /// exactly `hi` (and likewise for `lo`), and one that only reaches past
+    /// `hi` keeps its lower bound.
+    ///
+    /// Panics unless `lo <= hi`.
+    pub fn mul_clamped(self, other: EFloat32, lo: f32, hi: f32) -> EFloat32 {
+        (self * other).clamped(lo, hi)
+    }
+
+    /// Clamp into `[0, 1]`, for colors and weights.  The value and both
+    /// bounds are clamped, so an interval entirely below 0 (above 1) becomes
+    /// exactly 0 (1), and one straddling either end is cut off there.
+    pub fn clamp01(&self) -> EFloat32 {
+        self.clamped(0.0, 1.0)
+    }
+
+    /// Interpret the value as a probability, clamped into `[0, 1]` (see
+    /// `clamp01`).  Rounding can push a probability slightly out of range,
+    /// but never by more than its own error width, so as long as the
+    /// interval still reaches into `[0, 1]` the overshoot is treated as
+    /// noise.  An interval entirely outside is an error, as it points to a
+    /// real bug rather than rounding.
+    pub fn to_probability(&self) -> Result<EFloat32, ProbabilityError> {
+        if self.v.is_nan() || self.low.is_nan() || self.high.is_nan() {
+            Err(ProbabilityError::NaN)
+        } else if self.high < 0.0 {
+            Err(ProbabilityError::BelowZero)
+        } else if self.low > 1.0 {
+            Err(ProbabilityError::AboveOne)
+        } else {
+            Ok(self.clamp01())
+        }
+    }
+
+    // Clamp the value, the bounds and the precise shadow into [lo, hi].
+    // Clamping is monotone, so the bounds still contain the shadow.
+    fn clamped(&self, lo: f32, hi: f32) -> EFloat32 {
+        assert!(lo <= hi, "clamp range out of order: [{}, {}]", lo, hi);
+        let r = EFloat32 {
+            v: self.v.max(lo).min(hi),
+            low: self.low.max(lo).min(hi),
+            high: self.high.max(lo).min(hi),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise.max(lo as f64).min(hi as f64),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.max_rel_seen,
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// Draw a sample uniformly from `[low, high]`, for Monte-Carlo error
+    /// analysis where the bounds are too pessimistic.  The bounds must be
+    /// finite.
+    #[cfg(feature = "rand")]
+    pub fn sample_uniform(&self, rng: &mut impl Rng) -> f32 {
+        let u: f64 = rng.gen();
+        let x = self.low as f64 + u * (self.high as f64 - self.low as f64);
+        (x as f32).max(self.low).min(self.high)
+    }
+
+    /// Draw a sample from the triangular distribution over `[low, high]`
+    /// that peaks at the value, i.e. assuming the value is the most likely
+    /// and the bounds the least.  The bounds must be finite.
+    #[cfg(feature = "rand")]
+    pub fn sample_triangular(&self, rng: &mut impl Rng) -> f32 {
+        let (a, b, c) = (self.low as f64, self.high as f64, self.v as f64);
+        if a == b {
+            return self.v;
+        }
+        let u: f64 = rng.gen();
+        let x = if u < (c - a) / (b - a) {
+            a + (u * (b - a) * (c - a)).sqrt()
+        } else {
+            b - ((1.0 - u) * (b - a) * (b - c)).sqrt()
+        };
+        (x as f32).max(self.low).min(self.high)
+    }
+
+    /// The sign of the interval, see `IntervalSign`.
+    pub fn sign(&self) -> IntervalSign {
+        if self.low > 0.0 {
+            IntervalSign::Positive
+        } else if self.high < 0.0 {
+            IntervalSign::Negative
+        } else if self.low == 0.0 && self.high == 0.0 {
+            IntervalSign::Zero
+        } else {
+            IntervalSign::Straddling
+        }
+    }
+
+    /// Convert an exact rational, with bounds that straddle its true value.
+    ///
+    /// When the numerator and denominator are both exactly representable as
+    /// `f64` (within 2^53), the only rounding is in the division, and a fused
+    /// multiply-add finds which side of the true value the quotient fell on,
+    /// so the bounds are the two `f32`s on either side of it (or both the
+    /// value itself, if that is exact).  Otherwise the quotient is widened by
+    /// a couple of `f64` ulps first, which may occasionally widen the bounds
+    /// by one more `f32`.
+    #[cfg(feature = "num-rational")]
+    pub fn from_ratio(r: Ratio<i64>) -> EFloat32 {
+        const EXACT: f64 = 9007199254740992.0; // 2^53
+        let p = *r.numer() as f64;
+        let q = *r.denom() as f64;
+        let x = p / q;
+        let (low, high) = if p.abs() <= EXACT && q.abs() <= EXACT {
+            // x * q - p, exactly, has the sign of x minus the true value
+            // (flipped by a negative denominator)
+            let residual = x.mul_add(q, -p) * q.signum();
+            if residual > 0.0 {
+                (f64_to_f32_down(next_f64_down(x)), f64_to_f32_up(x))
+            } else if residual < 0.0 {
+                (f64_to_f32_down(x), f64_to_f32_up(next_f64_up(x)))
+            } else {
+                (f64_to_f32_down(x), f64_to_f32_up(x))
+            }
+        } else {
+            (
+                f64_to_f32_down(next_f64_down(next_f64_down(x))),
+                f64_to_f32_up(next_f64_up(next_f64_up(x))),
+            )
+        };
+        let ef = EFloat32 {
+            v: (x as f32).max(low).min(high),
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: x,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: high - low,
+        };
+        ef.check();
+        ef
+    }
+
+    /// The reciprocal square root, `1 / sqrt(self)`.
+    ///
+    /// This is computed in f64 in one go and rounded outward to the adjacent
+    /// `f32`s, rather than rounding outward after both the `sqrt` and the
+    /// `recip`.  It is only defined for positive values: an interval that is
+    /// entirely negative gives NaN, and one that reaches down to zero (or
+    /// below) gives an upper bound of infinity.
+    pub fn rsqrt(&self) -> EFloat32 {
+        if self.high < 0.0 {
+            return EFloat32::nan();
+        }
+        // The sqrt's rounding carries through the division as up to about an
+        // f64 ulp of relative error, and the division adds another half ulp,
+        // so step out two f64 ulps before rounding outward to f32.
+        let rsqrt = |x: f32| 1.0 / (x as f64).sqrt();
+        let r = EFloat32 {
+            v: rsqrt(self.v) as f32,
+            low: f64_to_f32_down(next_f64_down(next_f64_down(rsqrt(self.high)))),
+            high: if self.low <= 0.0 {
+                f32::INFINITY
+            } else {
+                f64_to_f32_up(next_f64_up(next_f64_up(rsqrt(self.low))))
+            },
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: 1.0 / self.precise.sqrt(),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        if self.low <= 0.0 {
+            // The value (or precise value) may be negative, with no root to
+            // check against.
+            return r;
+        }
+        r.check();
+        r
+    }
+
+    /// The classification of the lower and upper bounds.  When these differ
+    /// the interval spans categories (e.g. it is partly subnormal), which
+    /// `Float::classify` (looking only at the value) does not reveal.
+    pub fn classify_interval(&self) -> (FpCategory, FpCategory) {
+        (self.low.classify(), self.high.classify())
+    }
+
+    /// `self * a + b` with exact `f32` coefficients, as in evaluating a
+    /// polynomial.  With only two corners, `self.low * a + b` and
+    /// `self.high * a + b`, each is computed exactly in f64 (the product of
+    /// two `f32`s is exact, and TwoSum gives the error of the sum) and rounded
+    /// directly to the adjacent `f32`, so the bounds are never wider than
+    /// those of `Float::mul_add` and often an ulp tighter.
+    pub fn mul_add_f32(self, a: f32, b: f32) -> EFloat32 {
+        let round_down = |x: f32| {
+            let (s, err) = two_sum(x as f64 * a as f64, b as f64);
+            f64_to_f32_down(if err < 0.0 { next_f64_down(s) } else { s })
+        };
+        let round_up = |x: f32| {
+            let (s, err) = two_sum(x as f64 * a as f64, b as f64);
+            f64_to_f32_up(if err > 0.0 { next_f64_up(s) } else { s })
+        };
+        let (low, high) = if a >= 0.0 {
+            (round_down(self.low), round_up(self.high))
+        } else {
+            (round_down(self.high), round_up(self.low))
+        };
+        let r = EFloat32 {
+            v: self.v.mul_add(a, b),
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise * a as f64 + b as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// `1 - self`, as in Fresnel terms and blending weights.
+    ///
+    /// This is the same as `EFloat32::new(1.0) - self`, except that the
+    /// bounds are computed exactly (with TwoSum) and rounded directly to the
+    /// adjacent `f32`s instead of always being widened by an ulp.  For values
+    /// in `[0.5, 2]`, where the cancellation is worst, `1 - x` is exact
+    /// (Sterbenz), so the bounds do not widen at all.
+    pub fn one_minus(&self) -> EFloat32 {
+        let (low, err) = two_sum(1.0, -(self.high as f64));
+        let low = f64_to_f32_down(if err < 0.0 { next_f64_down(low) } else { low });
+        let (high, err) = two_sum(1.0, -(self.low as f64));
+        let high = f64_to_f32_up(if err > 0.0 { next_f64_up(high) } else { high });
+        let r = EFloat32 {
+            v: 1.0 - self.v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: 1.0 - self.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// The integer and fractional parts, as C's `modf`: `trunc()` and
+    /// `fract()`, both taking the sign of the value.
+    ///
+    /// Within a single integer band the integer part is exact and the
+    /// fractional part as tight as the input.  When the interval straddles an
+    /// integer, the integer part covers every integer it might truncate to
+    /// and the fractional part becomes the whole band (as in `fract`), so the
+    /// sum of the two parts still contains the original interval.
+    pub fn modf(&self) -> (EFloat32, EFloat32) {
+        (Float::trunc(*self), Float::fract(*self))
+    }
+
+    /// Reduce an angle modulo 2π into `[-π, π]`.
+    ///
+    /// The whole interval is shifted by the same multiple of 2π, so it stays
+    /// as tight as it was (plus a little for the error of 2π in `f64`).  Like
+    /// `fract`, when the interval straddles the wrap point at ±π, or is wider
+    /// than 2π, the result could be anywhere, so the bounds are the full
+    /// `[-π, π]`.
+    pub fn wrap_angle(&self) -> EFloat32 {
+        let two_pi = 2.0 * PI;
+        let wrap = |x: f64| x - ((x + PI) / two_pi).floor() * two_pi;
+        let (lo, hi) = (self.low as f64, self.high as f64);
+        let k = ((lo + PI) / two_pi).floor();
+        // covers the error of 2π as an f64, and the rounding of the product
+        // and difference; zero when no shift is needed
+        let slack = 2.0 * k.abs() * two_pi * f64::EPSILON;
+        let (wlo, whi) = (lo - k * two_pi - slack, hi - k * two_pi + slack);
+        if !lo.is_finite() || !hi.is_finite() || wlo < -PI || whi > PI {
+            let r = EFloat32 {
+                v: wrap(self.v as f64) as f32,
+                low: f64_to_f32_down(-PI),
+                high: f64_to_f32_up(PI),
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+                precise: wrap(self.precise),
+                #[cfg(feature = "peak-error")]
+                max_rel_seen: self.peak_relative_error(),
+                #[cfg(feature = "initial-error")]
+                initial_error: self.initial_error,
+            };
+            r.check();
+            return r;
+        }
+        let r = EFloat32 {
+            v: (self.v as f64 - k * two_pi) as f32,
+            low: f64_to_f32_down(wlo),
+            high: f64_to_f32_up(whi),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise - k * two_pi,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+}
+
+impl Add for EFloat32 {
+    type Output = EFloat32;
+
+    fn add(self, other: EFloat32) -> EFloat32 {
+        // Interval arithemetic addition, with the result rounded away from
+        // the value r.v in order to be conservative.
+        let (low, high) = widen_indeterminate(
+            next_f32_down(self.low + other.low),
+            next_f32_up(self.high + other.high),
+        );
+        let r = EFloat32 {
+            v: self.v + other.v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise + other.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + other.initial_error,
+        };
+        r.check();
+        #[cfg(feature = "infinity-events")]
+        note_infinity(&[self, other], &r);
+        #[cfg(feature = "log")]
+        warn_if_inaccurate("add", &r);
+        r
+    }
+}
+
+impl Sub for EFloat32 {
+    type Output = EFloat32;
+
+    fn sub(self, other: EFloat32) -> EFloat32 {
+        let (low, high) = widen_indeterminate(
+            next_f32_down(self.low - other.high),
+            next_f32_up(self.high - other.low),
+        );
+        let r = EFloat32 {
+            v: self.v - other.v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise - other.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + other.initial_error,
+        };
+        r.check();
+        #[cfg(feature = "infinity-events")]
+        note_infinity(&[self, other], &r);
+        #[cfg(feature = "log")]
+        warn_if_inaccurate("sub", &r);
+        r
+    }
+}
+
+impl Mul for EFloat32 {
+    type Output = EFloat32;
+
+    fn mul(self, other: EFloat32) -> EFloat32 {
+        if (self.is_exact() && self.v == 0.0 && !other.has_nan())
+            || (other.is_exact() && other.v == 0.0 && !self.has_nan())
+        {
+            // Exactly zero times anything but NaN is exactly zero, even where
+            // the corners below would give 0 * inf = NaN.  The value keeps
+            // the IEEE sign of the zero where the product is defined.
+            let v = self.v * other.v;
+            return EFloat32 {
+                v: if v.is_nan() { 0.0 } else { v },
+                low: 0.0,
+                high: 0.0,
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+                precise: 0.0,
+                #[cfg(feature = "peak-error")]
+                max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+                #[cfg(feature = "initial-error")]
+                initial_error: self.initial_error + other.initial_error,
+            };
+        }
+        let (low, high) = match (self.sign(), other.sign()) {
+            // With both operands on one side of zero the extremes are known
+            // without comparing corners (rounding is monotone, so this is
+            // identical to the general case below).
+            (IntervalSign::Positive, IntervalSign::Positive) => {
+                (self.low * other.low, self.high * other.high)
+            }
+            (IntervalSign::Negative, IntervalSign::Negative) => {
+                (self.high * other.high, self.low * other.low)
+            }
+            _ => {
+                let prod: [f32; 4] = [
+                    self.low * other.low,
+                    self.high * other.low,
+                    self.low * other.high,
+                    self.high * other.high,
+                ];
+                (
+                    prod[0].min(prod[1]).min(prod[2].min(prod[3])),
+                    prod[0].max(prod[1]).max(prod[2].max(prod[3])),
+                )
+            }
+        };
+
+        let (low, high) = widen_indeterminate(next_f32_down(low), next_f32_up(high));
+        let r = EFloat32 {
+            v: self.v * other.v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise * other.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + other.initial_error,
+        };
+        r.check();
+        #[cfg(feature = "infinity-events")]
+        note_infinity(&[self, other], &r);
+        #[cfg(feature = "log")]
+        warn_if_inaccurate("mul", &r);
+        r
+    }
+}
+
+impl Div for EFloat32 {
+    type Output = EFloat32;
+
+    fn div(self, other: EFloat32) -> EFloat32 {
+        if other.low < 0.0 && other.high > 0.0 {
+            // Bah. the interval we are dividing straddles zero, so just
+            // return an interval of everything.
+            let r = EFloat32 {
+                v: self.v / other.v,
+                low: -f32::INFINITY,
+                high: f32::INFINITY,
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+                precise: self.precise / other.precise,
+                #[cfg(feature = "peak-error")]
+                max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+                #[cfg(feature = "initial-error")]
+                initial_error: self.initial_error + other.initial_error,
+            };
+            #[cfg(feature = "infinity-events")]
+            note_infinity(&[self, other], &r);
+            #[cfg(feature = "log")]
+            warn_if_inaccurate("div", &r);
+            return r;
+        }
+        let prod: [f32; 4] = [
+            self.low / other.low,
+            self.high / other.low,
+            self.low / other.high,
+            self.high / other.high,
+        ];
+
+        let (low, high) = widen_indeterminate(
+            next_f32_down(prod[0].min(prod[1]).min(prod[2].min(prod[3]))),
+            next_f32_up(prod[0].max(prod[1]).max(prod[2].max(prod[3]))),
+        );
+        let r = EFloat32 {
+            v: self.v / other.v,
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise / other.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + other.initial_error,
+        };
+        r.check();
+        #[cfg(feature = "infinity-events")]
+        note_infinity(&[self, other], &r);
+        #[cfg(feature = "log")]
+        warn_if_inaccurate("div", &r);
+        r
+    }
+}
+
+impl Rem for EFloat32 {
+    type Output = EFloat32;
+
+    fn rem(self, other: EFloat32) -> EFloat32 {
+        if other.low < 0.0 && other.high > 0.0 {
+            // Bah. the interval we are dividing straddles zero, so just
+            // return an interval of everything.
+            let r = EFloat32 {
+                v: self.v % other.v,
+                low: -f32::INFINITY,
+                high: f32::INFINITY,
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+                precise: self.precise % other.precise,
+                #[cfg(feature = "peak-error")]
+                max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+                #[cfg(feature = "initial-error")]
+                initial_error: self.initial_error + other.initial_error,
+            };
+            #[cfg(feature = "infinity-events")]
+            note_infinity(&[self, other], &r);
+            #[cfg(feature = "log")]
+            warn_if_inaccurate("rem", &r);
+            return r;
+        }
+        let prod: [f32; 4] = [
+            self.low % other.low,
+            self.high % other.low,
+            self.low % other.high,
+            self.high % other.high,
+        ];
+
+        let r = EFloat32 {
+            v: self.v % other.v,
+            low: next_f32_down(prod[0].min(prod[1]).min(prod[2].min(prod[3]))),
+            high: next_f32_up(prod[0].max(prod[1]).max(prod[2].max(prod[3]))),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise % other.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + other.initial_error,
+        };
+        r.check();
+        #[cfg(feature = "infinity-events")]
+        note_infinity(&[self, other], &r);
+        #[cfg(feature = "log")]
+        warn_if_inaccurate("rem", &r);
+        r
+    }
+}
+
+impl Neg for EFloat32 {
+    type Output = EFloat32;
+
+    fn neg(self) -> EFloat32 {
+        let r = EFloat32 {
+            v: -self.v,
+            low: -self.high,
+            high: -self.low,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: -self.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+}
+
+impl Sum for EFloat32 {
+    fn sum<I: Iterator<Item = EFloat32>>(iter: I) -> EFloat32 {
+        iter.fold(EFloat32::zero(), |acc, x| acc + x)
+    }
+}
+
+impl PartialEq for EFloat32 {
+    fn eq(&self, other: &EFloat32) -> bool {
+        self.v == other.v
+    }
+}
+
+impl PartialOrd for EFloat32 {
+    fn partial_cmp(&self, other: &EFloat32) -> Option<Ordering> {
+        self.v.partial_cmp(&other.v)
+    }
+}
+
+// Comparisons against a plain `f32` look only at the value, like the ones
+// between two `EFloat32`s.  See `definitely_lt()` and friends for comparisons
+// that take the bounds into account.
+impl PartialEq<f32> for EFloat32 {
+    fn eq(&self, other: &f32) -> bool {
+        self.v == *other
+    }
+}
+
+impl PartialOrd<f32> for EFloat32 {
+    fn partial_cmp(&self, other: &f32) -> Option<Ordering> {
+        self.v.partial_cmp(other)
+    }
+}
+
+impl Zero for EFloat32 {
+    fn zero() -> EFloat32 {
+        EFloat32 {
+            v: 0.0,
+            low: 0.0,
+            high: 0.0,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: 0.0,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: 0.0,
+        }
+    }
+
+    /// Whether zero is within the bounds, i.e. the value might be zero.  See
+    /// `is_exact_zero()` for whether it definitely is.
+    fn is_zero(&self) -> bool {
+        self.low <= 0.0 && self.high >= 0.0
+    }
+}
+
+impl One for EFloat32 {
+    fn one() -> EFloat32 {
+        EFloat32 {
+            v: 1.0,
+            low: 1.0,
+            high: 1.0,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: 1.0,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: 0.0,
+        }
+    }
+
+    fn is_one(&self) -> bool {
+        self.low <= 1.0 && self.high >= 1.0
+    }
+}
+
+impl Num for EFloat32 {
+    type FromStrRadixErr = ParseFloatError;
+
+    fn from_str_radix(src: &str, radix: u32) -> Result<EFloat32, ParseFloatError> {
+        let f = f32::from_str_radix(src, radix)?;
+        Ok(EFloat32 {
+            v: f,
+            low: f,
+            high: f,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: f as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: 0.0,
+        })
+    }
+}
+
+impl fmt::Display for EFloat32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format_with(ErrorStyle::PlusMinus))
+    }
+}
+
+/// Parses either a plain number, which (like `Num::from_str_radix`) is taken
+/// to be exact, or a number with an error written as `value±error` or
+/// `value+/-error`, which is equivalent to `new_with_err(value, error)`.
+/// Whitespace around either part is ignored.  A negative or NaN error is
+/// rejected as invalid.
+impl FromStr for EFloat32 {
+    type Err = ParseFloatError;
+
+    fn from_str(src: &str) -> Result<EFloat32, ParseFloatError> {
+        let split = src
+            .find('±')
+            .map(|i| (&src[..i], &src[i + '±'.len_utf8()..]))
+            .or_else(|| src.find("+/-").map(|i| (&src[..i], &src[i + 3..])));
+        match split {
+            Some((value, err)) => {
+                let value = f32::from_str_radix(value.trim(), 10)?;
+                let err = f32::from_str_radix(err.trim(), 10)?;
+                if err.is_nan() || err < 0.0 {
+                    return Err(ParseFloatError {
+                        kind: FloatErrorKind::Invalid,
+                    });
+                }
+                Ok(EFloat32::new_with_err(value, err))
+            }
+            None => EFloat32::from_str_radix(src.trim(), 10),
+        }
+    }
+}
+
+impl ToPrimitive for EFloat32 {
+    fn to_i64(&self) -> Option<i64> {
+        self.v.to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.v.to_u64()
+    }
+
+    fn to_isize(&self) -> Option<isize> {
+        self.v.to_isize()
     }
 
     fn to_i8(&self) -> Option<i8> {
         self.v.to_i8()
     }
 
-    fn to_i16(&self) -> Option<i16> {
-        self.v.to_i16()
+    fn to_i16(&self) -> Option<i16> {
+        self.v.to_i16()
+    }
+
+    fn to_i32(&self) -> Option<i32> {
+        self.v.to_i32()
+    }
+
+    fn to_usize(&self) -> Option<usize> {
+        self.v.to_usize()
+    }
+
+    fn to_u8(&self) -> Option<u8> {
+        self.v.to_u8()
+    }
+
+    fn to_u16(&self) -> Option<u16> {
+        self.v.to_u16()
+    }
+
+    fn to_u32(&self) -> Option<u32> {
+        self.v.to_u32()
+    }
+
+    fn to_f32(&self) -> Option<f32> {
+        self.v.to_f32()
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        self.v.to_f64()
+    }
+}
+
+impl NumCast for EFloat32 {
+    #[inline]
+    fn from<T: ToPrimitive>(n: T) -> Option<EFloat32> {
+        n.to_f32().map(|f| EFloat32 {
+            v: f,
+            low: f,
+            high: f,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: f as f64,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: 0.0,
+        })
+    }
+}
+
+impl Float for EFloat32 {
+    fn nan() -> EFloat32 {
+        EFloat32 {
+            v: f32::NAN,
+            low: f32::NAN,
+            high: f32::NAN,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: f64::NAN,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: 0.0,
+        }
+    }
+
+    fn infinity() -> EFloat32 {
+        EFloat32::new(f32::INFINITY)
+    }
+
+    fn neg_infinity() -> EFloat32 {
+        EFloat32::new(f32::NEG_INFINITY)
+    }
+
+    fn neg_zero() -> EFloat32 {
+        EFloat32::new(-0.0)
+    }
+
+    fn min_value() -> EFloat32 {
+        EFloat32::new(f32::MIN)
+    }
+
+    fn min_positive_value() -> EFloat32 {
+        EFloat32::new(f32::MIN_POSITIVE)
+    }
+
+    fn max_value() -> EFloat32 {
+        EFloat32::new(f32::MAX)
+    }
+
+    fn is_nan(self) -> bool {
+        self.v.is_nan()
+    }
+
+    fn is_infinite(self) -> bool {
+        self.v.is_infinite()
+    }
+
+    fn is_finite(self) -> bool {
+        self.v.is_finite()
+    }
+
+    fn is_normal(self) -> bool {
+        self.v.is_normal()
+    }
+
+    fn classify(self) -> FpCategory {
+        self.v.classify()
+    }
+
+    fn floor(self) -> EFloat32 {
+        self.monotone_exact(f64::floor)
+    }
+
+    fn ceil(self) -> EFloat32 {
+        self.monotone_exact(f64::ceil)
+    }
+
+    fn round(self) -> EFloat32 {
+        self.monotone_exact(f64::round)
+    }
+
+    fn trunc(self) -> EFloat32 {
+        self.monotone_exact(f64::trunc)
+    }
+
+    fn fract(self) -> EFloat32 {
+        if self.low.trunc() == self.high.trunc() {
+            // fract is increasing (and exact) within a single integer band
+            return self.monotone_exact(f64::fract);
+        }
+        // The interval straddles an integer, so the fractional part could be
+        // anything in the band, which takes the sign of the value.
+        let r = EFloat32 {
+            v: self.v.fract(),
+            low: if self.low < 0.0 { -1.0 } else { 0.0 },
+            high: if self.high > 0.0 { 1.0 } else { 0.0 },
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise.fract(),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error(),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    fn abs(self) -> EFloat32 {
+        EFloat32::abs(&self)
+    }
+
+    fn signum(self) -> EFloat32 {
+        self.monotone_exact(f64::signum)
+    }
+
+    fn is_sign_positive(self) -> bool {
+        self.v.is_sign_positive()
+    }
+
+    fn is_sign_negative(self) -> bool {
+        self.v.is_sign_negative()
+    }
+
+    /// `self * a + b` with a single rounding, which gives tighter bounds than
+    /// a separate multiply and add.
+    fn mul_add(self, a: EFloat32, b: EFloat32) -> EFloat32 {
+        let corners = [
+            (self.low, a.low),
+            (self.high, a.low),
+            (self.low, a.high),
+            (self.high, a.high),
+        ];
+        let mut low = f32::INFINITY;
+        let mut high = f32::NEG_INFINITY;
+        for &(x, y) in corners.iter() {
+            low = low.min(x.mul_add(y, b.low));
+            high = high.max(x.mul_add(y, b.high));
+        }
+        let r = EFloat32 {
+            v: self.v.mul_add(a.v, b.v),
+            low: next_f32_down(low),
+            high: next_f32_up(high),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise * a.precise + b.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self
+                .peak_relative_error()
+                .max(a.peak_relative_error())
+                .max(b.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + a.initial_error + b.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    fn recip(self) -> EFloat32 {
+        EFloat32::recip(&self)
+    }
+
+    /// By repeated squaring with `sqr`, which takes `O(log n)` steps rather
+    /// than `n`, and keeps every power of an interval straddling zero from
+    /// picking up a spurious sign change at each step.  For a base away from
+    /// zero the bounds come out about the same as repeated multiplication,
+    /// since fewer roundings are each amplified by the squarings after them.
+    fn powi(self, n: i32) -> EFloat32 {
+        if n == 0 {
+            return EFloat32::one();
+        }
+        let mut result: Option<EFloat32> = None;
+        let mut square = self;
+        let mut e = n.unsigned_abs();
+        loop {
+            if e & 1 == 1 {
+                result = Some(match result {
+                    None => square,
+                    Some(r) => r * square,
+                });
+            }
+            e >>= 1;
+            if e == 0 {
+                break;
+            }
+            square = square.sqr();
+        }
+        let r = result.unwrap();
+        if n < 0 {
+            EFloat32::recip(&r)
+        } else {
+            r
+        }
+    }
+
+    fn powf(self, n: EFloat32) -> EFloat32 {
+        if self.low < 0.0 {
+            if n.low == n.high && n.v == n.v.trunc() && n.v.abs() < i32::MAX as f32 {
+                return self.powi(n.v as i32);
+            }
+            // a possibly-negative base to a non-integer power is undefined
+            return EFloat32 {
+                v: self.v.powf(n.v),
+                low: f32::NAN,
+                high: f32::NAN,
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+                precise: self.precise.powf(n.precise),
+                #[cfg(feature = "peak-error")]
+                max_rel_seen: self.peak_relative_error().max(n.peak_relative_error()),
+                #[cfg(feature = "initial-error")]
+                initial_error: self.initial_error + n.initial_error,
+            };
+        }
+        // With a non-negative base, x^y is monotone in each argument
+        // separately, so the extremes are at the corners.
+        let corners = [
+            (self.low as f64).powf(n.low as f64),
+            (self.high as f64).powf(n.low as f64),
+            (self.low as f64).powf(n.high as f64),
+            (self.high as f64).powf(n.high as f64),
+        ];
+        let r = EFloat32 {
+            v: self.v.powf(n.v),
+            low: next_f32_down(corners.iter().cloned().fold(f64::INFINITY, f64::min) as f32),
+            high: next_f32_up(corners.iter().cloned().fold(f64::NEG_INFINITY, f64::max) as f32),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise.powf(n.precise),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error().max(n.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + n.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    fn sqrt(self) -> EFloat32 {
+        EFloat32::sqrt(&self)
+    }
+
+    fn exp(self) -> EFloat32 {
+        self.monotone_increasing(f64::exp)
+    }
+
+    fn exp2(self) -> EFloat32 {
+        self.monotone_increasing(f64::exp2)
+    }
+
+    fn ln(self) -> EFloat32 {
+        EFloat32::ln(&self)
+    }
+
+    fn log(self, base: EFloat32) -> EFloat32 {
+        EFloat32::log(&self, base)
+    }
+
+    fn log2(self) -> EFloat32 {
+        self.monotone_increasing(f64::log2)
+    }
+
+    fn log10(self) -> EFloat32 {
+        self.monotone_increasing(f64::log10)
+    }
+
+    /// The interval maximum: each bound is the maximum of the corresponding
+    /// bounds, so the result covers whichever operand is really larger.
+    fn max(self, other: EFloat32) -> EFloat32 {
+        let r = EFloat32 {
+            v: self.v.max(other.v),
+            low: self.low.max(other.low),
+            high: self.high.max(other.high),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise.max(other.precise),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + other.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    /// The interval minimum: each bound is the minimum of the corresponding
+    /// bounds, so the result covers whichever operand is really smaller.
+    fn min(self, other: EFloat32) -> EFloat32 {
+        let r = EFloat32 {
+            v: self.v.min(other.v),
+            low: self.low.min(other.low),
+            high: self.high.min(other.high),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise.min(other.precise),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + other.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    fn abs_sub(self, other: EFloat32) -> EFloat32 {
+        EFloat32::abs_sub(self, other)
+    }
+
+    fn cbrt(self) -> EFloat32 {
+        self.monotone_increasing(f64::cbrt)
+    }
+
+    fn hypot(self, other: EFloat32) -> EFloat32 {
+        // hypot is increasing in the magnitude of each argument
+        let x = EFloat32::abs(&self);
+        let y = EFloat32::abs(&other);
+        let r = EFloat32 {
+            v: self.v.hypot(other.v),
+            low: next_f32_down((x.low as f64).hypot(y.low as f64) as f32),
+            high: next_f32_up((x.high as f64).hypot(y.high as f64) as f32),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise.hypot(other.precise),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + other.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    fn sin(self) -> EFloat32 {
+        self.periodic(f64::sin, PI / 2.0)
+    }
+
+    fn cos(self) -> EFloat32 {
+        self.periodic(f64::cos, 0.0)
+    }
+
+    fn tan(self) -> EFloat32 {
+        let (lo, hi) = (self.low as f64, self.high as f64);
+        let k = ((lo - PI / 2.0) / PI).ceil();
+        if !lo.is_finite() || !hi.is_finite() || PI / 2.0 + k * PI <= hi {
+            // the interval includes an asymptote
+            return EFloat32 {
+                v: self.v.tan(),
+                low: f32::NEG_INFINITY,
+                high: f32::INFINITY,
+                #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+                precise: self.precise.tan(),
+                #[cfg(feature = "peak-error")]
+                max_rel_seen: self.peak_relative_error(),
+                #[cfg(feature = "initial-error")]
+                initial_error: self.initial_error,
+            };
+        }
+        self.monotone_increasing(f64::tan)
+    }
+
+    fn asin(self) -> EFloat32 {
+        self.monotone_increasing(f64::asin)
+    }
+
+    fn acos(self) -> EFloat32 {
+        self.monotone_decreasing(f64::acos)
+    }
+
+    fn atan(self) -> EFloat32 {
+        self.monotone_increasing(f64::atan)
+    }
+
+    /// The angle of the point `(other, self)`, i.e. `self` is y and `other`
+    /// is x.
+    fn atan2(self, other: EFloat32) -> EFloat32 {
+        let (low, high) = if self.low <= 0.0 && self.high >= 0.0 && other.low <= 0.0 {
+            // The box touches the origin, where the angle is undefined, or
+            // the negative x axis, where it jumps from pi to -pi, so all we
+            // can say is that it is somewhere in [-pi, pi].
+            (f64_to_f32_down(-PI), f64_to_f32_up(PI))
+        } else {
+            // Otherwise the angle is continuous over the box, and its extremes
+            // are at the corners.
+            let corners = [
+                (self.low as f64).atan2(other.low as f64),
+                (self.high as f64).atan2(other.low as f64),
+                (self.low as f64).atan2(other.high as f64),
+                (self.high as f64).atan2(other.high as f64),
+            ];
+            (
+                next_f32_down(corners.iter().cloned().fold(f64::INFINITY, f64::min) as f32),
+                next_f32_up(corners.iter().cloned().fold(f64::NEG_INFINITY, f64::max) as f32),
+            )
+        };
+        let r = EFloat32 {
+            v: self.v.atan2(other.v),
+            low,
+            high,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise.atan2(other.precise),
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak_relative_error().max(other.peak_relative_error()),
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error + other.initial_error,
+        };
+        r.check();
+        r
+    }
+
+    fn sin_cos(self) -> (EFloat32, EFloat32) {
+        (self.sin(), self.cos())
+    }
+
+    fn exp_m1(self) -> EFloat32 {
+        EFloat32::exp_m1(&self)
+    }
+
+    fn ln_1p(self) -> EFloat32 {
+        EFloat32::ln_1p(&self)
+    }
+
+    fn sinh(self) -> EFloat32 {
+        EFloat32::sinh(&self)
+    }
+
+    fn cosh(self) -> EFloat32 {
+        EFloat32::cosh(&self)
+    }
+
+    fn tanh(self) -> EFloat32 {
+        EFloat32::tanh(&self)
+    }
+
+    fn asinh(self) -> EFloat32 {
+        self.monotone_increasing(f64::asinh)
+    }
+
+    fn acosh(self) -> EFloat32 {
+        self.monotone_increasing(f64::acosh)
+    }
+
+    fn atanh(self) -> EFloat32 {
+        self.monotone_increasing(f64::atanh)
+    }
+
+    fn integer_decode(self) -> (u64, i16, i8) {
+        self.v.integer_decode()
+    }
+}
+
+impl ApproxEq for EFloat32 {
+    type Margin = ();
+
+    fn approx_eq(&self, other: &Self, _margin: &()) -> bool {
+        self.overlaps(other)
+    }
+}
+
+// Safe because without precise tracking EFloat32 is made only of f32s under
+// #[repr(C)], with no padding, and any bit pattern is a valid f32.  The crate
+// is `#![forbid(unsafe_code)]` only without the bytemuck feature and outside
+// tests; otherwise it is `deny`, and these impls (like the tests calling C's
+// `fmodf`) opt back in with a local `allow`.
+#[allow(unsafe_code)]
+#[cfg(all(
+    feature = "bytemuck",
+    not(any(debug_assertions, feature = "precise-tracking"))
+))]
+unsafe impl Zeroable for EFloat32 {}
+
+#[cfg(all(
+    feature = "bytemuck",
+    not(any(debug_assertions, feature = "precise-tracking"))
+))]
+#[allow(unsafe_code)]
+unsafe impl Pod for EFloat32 {}
+
+fn f32_to_bits(f: f32) -> u32 {
+    f.to_bits()
+}
+
+fn bits_to_f32(u: u32) -> f32 {
+    f32::from_bits(u)
+}
+
+// The position of a non-NaN `f` among all f32s, counting up from zero (which
+// 0.0 and -0.0 share), so that adjacent floats differ by one.
+fn ordered_f32(f: f32) -> i64 {
+    let u = f32_to_bits(f);
+    if u & 0x8000_0000 != 0 {
+        -((u & 0x7fff_ffff) as i64)
+    } else {
+        u as i64
+    }
+}
+
+// The inverse of ordered_f32, saturating at the infinities.
+fn from_ordered_f32(o: i64) -> f32 {
+    let inf = f32_to_bits(f32::INFINITY) as i64;
+    let o = o.clamp(-inf, inf);
+    if o < 0 {
+        bits_to_f32((-o) as u32 | 0x8000_0000)
+    } else {
+        bits_to_f32(o as u32)
+    }
+}
+
+// The number of representable f32s between `a` and `b`, counting across zero
+// (where 0.0 and -0.0 are the same step).
+fn ulps_between(a: f32, b: f32) -> u64 {
+    (ordered_f32(b) - ordered_f32(a)).unsigned_abs()
+}
+
+pub fn next_f32_up(f: f32) -> f32 {
+    if f.is_infinite() && f > 0.0 {
+        f
+    } else if f == -0.0 && f.is_sign_negative() {
+        0.0
+    } else {
+        let mut u = f32_to_bits(f);
+        if f >= 0.0 {
+            u += 1;
+        } else {
+            u -= 1;
+        }
+        bits_to_f32(u)
+    }
+}
+
+pub fn next_f32_down(f: f32) -> f32 {
+    if f.is_infinite() && f < 0.0 {
+        f
+    } else if f == 0.0 && f.is_sign_positive() {
+        -0.0
+    } else {
+        let mut u = f32_to_bits(f);
+        if f <= -0.0 {
+            u += 1;
+        } else {
+            u -= 1;
+        }
+        bits_to_f32(u)
+    }
+}
+
+// The operators' policy for IEEE indeterminate forms (inf - inf, 0 * inf,
+// inf / inf, 0 / 0) in the bounds: a bound that came out NaN is widened to
+// the infinity on its side, so the interval covers everything rather than
+// breaking `check()` and every comparison after it.  The value is left as
+// IEEE computes it, which may well be NaN.  (The one exception is `Mul` by
+// an exact zero, which is an exact zero whatever the other operand.)
+pub(crate) fn widen_indeterminate(low: f32, high: f32) -> (f32, f32) {
+    (
+        if low.is_nan() { f32::NEG_INFINITY } else { low },
+        if high.is_nan() { f32::INFINITY } else { high },
+    )
+}
+
+#[cfg(feature = "infinity-events")]
+thread_local! {
+    static INFINITY_EVENTS: ::std::cell::Cell<u64> = const { ::std::cell::Cell::new(0) };
+}
+
+/// The number of times, on this thread, that an arithmetic operator or
+/// `recip` produced an infinite bound from operands whose bounds were all
+/// finite (typically by dividing by an interval that includes zero).  Once
+/// a bound is infinite it silently flows through everything computed from
+/// it, so this helps find where a pipeline first went unstable.
+#[cfg(feature = "infinity-events")]
+pub fn infinity_events() -> u64 {
+    INFINITY_EVENTS.with(|n| n.get())
+}
+
+/// Reset the count returned by `infinity_events()` on this thread to zero.
+#[cfg(feature = "infinity-events")]
+pub fn reset_infinity_events() {
+    INFINITY_EVENTS.with(|n| n.set(0));
+}
+
+#[cfg(feature = "infinity-events")]
+fn note_infinity(operands: &[EFloat32], r: &EFloat32) {
+    if (r.low.is_infinite() || r.high.is_infinite())
+        && operands
+            .iter()
+            .all(|x| x.low.is_finite() && x.high.is_finite())
+    {
+        INFINITY_EVENTS.with(|n| n.set(n.get() + 1));
+    }
+}
+
+#[cfg(feature = "log")]
+thread_local! {
+    static ERROR_WARNING_THRESHOLD: ::std::cell::Cell<f32> =
+        const { ::std::cell::Cell::new(f32::INFINITY) };
+}
+
+/// Log a warning (with the `log` crate) whenever an arithmetic operator or
+/// `recip` on this thread produces a result whose `relative_error_estimate()`
+/// is above `threshold`, naming the operation and the bounds.  This finds
+/// where precision is being lost without instrumenting the code by hand.
+/// The default threshold is infinity, i.e. never warn.
+#[cfg(feature = "log")]
+pub fn set_error_warning_threshold(threshold: f32) {
+    ERROR_WARNING_THRESHOLD.with(|t| t.set(threshold));
+}
+
+/// The threshold set by `set_error_warning_threshold()` on this thread.
+#[cfg(feature = "log")]
+pub fn error_warning_threshold() -> f32 {
+    ERROR_WARNING_THRESHOLD.with(|t| t.get())
+}
+
+#[cfg(feature = "log")]
+fn warn_if_inaccurate(op: &str, r: &EFloat32) {
+    let threshold = error_warning_threshold();
+    let rel = r.relative_error_estimate();
+    if rel > threshold {
+        warn!(
+            "{}: relative error {} exceeds {}, value {} in [{}, {}]",
+            op, rel, threshold, r.v, r.low, r.high
+        );
+    }
+}
+
+// Round an f32 to an f16 that is no greater than it.
+#[cfg(feature = "half")]
+fn f32_to_f16_down(x: f32) -> f16 {
+    let h = f16::from_f32(x);
+    if h.to_f32() > x {
+        // step down one f16, as next_f32_down does
+        if h.to_f32() == 0.0 {
+            f16::from_bits(0x8001)
+        } else if h.to_f32() > 0.0 {
+            f16::from_bits(h.to_bits() - 1)
+        } else {
+            f16::from_bits(h.to_bits() + 1)
+        }
+    } else {
+        h
+    }
+}
+
+// Round an f32 to an f16 that is no less than it.
+#[cfg(feature = "half")]
+fn f32_to_f16_up(x: f32) -> f16 {
+    -f32_to_f16_down(-x)
+}
+
+// Round an f64 to an f32 that is no greater than it.
+fn f64_to_f32_down(x: f64) -> f32 {
+    let f = x as f32;
+    if f as f64 > x {
+        next_f32_down(f)
+    } else {
+        f
+    }
+}
+
+// Round an f64 to an f32 that is no less than it.
+fn f64_to_f32_up(x: f64) -> f32 {
+    let f = x as f32;
+    if (f as f64) < x {
+        next_f32_up(f)
+    } else {
+        f
+    }
+}
+
+fn next_f64_up(f: f64) -> f64 {
+    if f.is_infinite() && f > 0.0 {
+        f
+    } else if f == 0.0 {
+        f64::MIN_POSITIVE * f64::EPSILON
+    } else if f > 0.0 {
+        f64::from_bits(f.to_bits() + 1)
+    } else {
+        f64::from_bits(f.to_bits() - 1)
+    }
+}
+
+fn next_f64_down(f: f64) -> f64 {
+    -next_f64_up(-f)
+}
+
+// Add two f64s, also returning the rounding error of the sum (Knuth's TwoSum),
+// so that `a + b == sum + err` exactly.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let bb = sum - a;
+    let err = (a - (sum - bb)) + (b - bb);
+    (sum, err)
+}
+
+// As `a - b`, but each bound is rounded outward only if the difference of
+// the bounds is actually inexact, so differences of nearby (or equal) exact
+// values stay exact.
+fn sub_directed(a: EFloat32, b: EFloat32) -> EFloat32 {
+    if !(a.low.is_finite() && a.high.is_finite() && b.low.is_finite() && b.high.is_finite()) {
+        return a - b;
+    }
+    let (low, err) = two_sum(a.low as f64, -(b.high as f64));
+    let low = f64_to_f32_down(if err < 0.0 { next_f64_down(low) } else { low });
+    let (high, err) = two_sum(a.high as f64, -(b.low as f64));
+    let high = f64_to_f32_up(if err > 0.0 { next_f64_up(high) } else { high });
+    let r = EFloat32 {
+        v: a.v - b.v,
+        low,
+        high,
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        precise: a.precise - b.precise,
+        #[cfg(feature = "peak-error")]
+        max_rel_seen: a.peak_relative_error().max(b.peak_relative_error()),
+        #[cfg(feature = "initial-error")]
+        initial_error: a.initial_error + b.initial_error,
+    };
+    r.check();
+    r
+}
+
+/// The running partial sums of the values, as `+` would compute them, each
+/// paired with its `absolute_error()`.  This is for watching error build up
+/// over a summation, e.g. to compare orderings (small magnitudes first
+/// usually grows the error more slowly).
+pub fn scan_errors<I: IntoIterator<Item = EFloat32>>(
+    iter: I,
+) -> impl Iterator<Item = (EFloat32, f32)> {
+    iter.into_iter().scan(EFloat32::zero(), |acc, x| {
+        *acc = *acc + x;
+        Some((*acc, acc.absolute_error()))
+    })
+}
+
+/// Sum with compensated (Kahan-Babuska) summation.
+///
+/// The value is compensated, so it is much closer to the true sum than a
+/// plain `Sum` when terms differ wildly in magnitude.  The bounds are summed
+/// in `f64`, rounding outward only when a step was actually inexact, and are
+/// rounded outward to `f32` once at the end, so they stay conservative while
+/// avoiding the ulp of widening that every `+` adds.
+pub fn kahan_sum<I: IntoIterator<Item = EFloat32>>(iter: I) -> EFloat32 {
+    let mut sum = 0.0f32;
+    let mut compensation = 0.0f32;
+    let mut low = 0.0f64;
+    let mut high = 0.0f64;
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+    let mut precise = 0.0f64;
+    #[cfg(feature = "peak-error")]
+    let mut peak = 0.0f32;
+    #[cfg(feature = "initial-error")]
+    let mut initial_error = 0.0f32;
+
+    for x in iter {
+        let t = sum + x.v;
+        if sum.abs() >= x.v.abs() {
+            compensation += (sum - t) + x.v;
+        } else {
+            compensation += (x.v - t) + sum;
+        }
+        sum = t;
+
+        let (l, err) = two_sum(low, x.low as f64);
+        low = if err < 0.0 { next_f64_down(l) } else { l };
+        let (h, err) = two_sum(high, x.high as f64);
+        high = if err > 0.0 { next_f64_up(h) } else { h };
+
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        {
+            precise += x.precise;
+        }
+        #[cfg(feature = "peak-error")]
+        {
+            peak = peak.max(x.peak_relative_error());
+        }
+        #[cfg(feature = "initial-error")]
+        {
+            initial_error += x.initial_error;
+        }
+    }
+
+    let r = EFloat32 {
+        v: sum + compensation,
+        low: f64_to_f32_down(low),
+        high: f64_to_f32_up(high),
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        precise,
+        #[cfg(feature = "peak-error")]
+        max_rel_seen: peak,
+        #[cfg(feature = "initial-error")]
+        initial_error,
+    };
+    r.check();
+    r
+}
+
+/// Accumulates a sum of products, such as an element of a matrix product,
+/// without widening the bounds at every step.
+///
+/// The value is accumulated with fused multiply-adds.  The bounds are
+/// accumulated in `f64`, where each corner product of two `f32`s is exact and
+/// the sums are rounded outward only when a step was actually inexact, and
+/// are rounded outward to `f32` once at the end in `into_efloat()`.
+#[derive(Debug, Clone, Copy)]
+pub struct MulAddAccumulator {
+    v: f32,
+    low: f64,
+    high: f64,
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+    precise: f64,
+    #[cfg(feature = "peak-error")]
+    peak: f32,
+    #[cfg(feature = "initial-error")]
+    initial_error: f32,
+}
+
+impl MulAddAccumulator {
+    /// An accumulator holding an exact zero.
+    pub fn new() -> MulAddAccumulator {
+        MulAddAccumulator {
+            v: 0.0,
+            low: 0.0,
+            high: 0.0,
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: 0.0,
+            #[cfg(feature = "peak-error")]
+            peak: 0.0,
+            #[cfg(feature = "initial-error")]
+            initial_error: 0.0,
+        }
+    }
+
+    /// Add `a * b` to the sum.
+    pub fn accumulate(&mut self, a: EFloat32, b: EFloat32) {
+        let corners = [
+            a.low as f64 * b.low as f64,
+            a.high as f64 * b.low as f64,
+            a.low as f64 * b.high as f64,
+            a.high as f64 * b.high as f64,
+        ];
+        let min = corners.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = corners.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        self.v = a.v.mul_add(b.v, self.v);
+        let (l, err) = two_sum(self.low, min);
+        self.low = if err < 0.0 { next_f64_down(l) } else { l };
+        let (h, err) = two_sum(self.high, max);
+        self.high = if err > 0.0 { next_f64_up(h) } else { h };
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        {
+            self.precise += a.precise * b.precise;
+        }
+        #[cfg(feature = "peak-error")]
+        {
+            self.peak = self
+                .peak
+                .max(a.peak_relative_error())
+                .max(b.peak_relative_error());
+        }
+        #[cfg(feature = "initial-error")]
+        {
+            self.initial_error += a.initial_error + b.initial_error;
+        }
+    }
+
+    /// The sum so far.
+    pub fn into_efloat(self) -> EFloat32 {
+        let r = EFloat32 {
+            v: self.v,
+            low: f64_to_f32_down(self.low),
+            high: f64_to_f32_up(self.high),
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            precise: self.precise,
+            #[cfg(feature = "peak-error")]
+            max_rel_seen: self.peak,
+            #[cfg(feature = "initial-error")]
+            initial_error: self.initial_error,
+        };
+        r.check();
+        r
+    }
+}
+
+impl Default for MulAddAccumulator {
+    fn default() -> MulAddAccumulator {
+        MulAddAccumulator::new()
+    }
+}
+
+/// The arithmetic mean `(a + b) / 2`, computed as `a/2 + b/2` so that it does
+/// not overflow when `a + b` would.  The halving is exact, so only the
+/// addition widens the bounds.
+pub fn arithmetic_mean(a: EFloat32, b: EFloat32) -> EFloat32 {
+    a.halve() + b.halve()
+}
+
+/// The geometric mean `sqrt(a * b)`.
+///
+/// # Panics
+///
+/// Panics if either interval includes negative values, where the geometric
+/// mean is not defined.
+pub fn geometric_mean(a: EFloat32, b: EFloat32) -> EFloat32 {
+    assert!(
+        a.low >= 0.0 && b.low >= 0.0,
+        "geometric mean of a possibly negative value"
+    );
+    (a * b).sqrt()
+}
+
+/// The weighted mean `sum(w * x) / sum(w)` of `(x, w)` pairs, with the
+/// weights taken to be exact.
+///
+/// An empty slice gives an exact zero rather than an error, since there is
+/// nothing to be uncertain about.  The weights should have a positive sum.
+pub fn weighted_mean(values: &[(EFloat32, f32)]) -> EFloat32 {
+    if values.is_empty() {
+        return EFloat32::zero();
+    }
+    let total: EFloat32 = values.iter().map(|&(_, w)| EFloat32::new(w)).sum();
+    let weighted: EFloat32 = values.iter().map(|&(x, w)| x * EFloat32::new(w)).sum();
+    weighted / total
+}
+
+/// Normalize the vector `(x, y, z)` to unit length.
+///
+/// The squared length is formed with `powi(2)`, which (unlike `x * x`) knows
+/// both factors are the same value and so never goes negative, and the
+/// reciprocal of the length is computed once and shared by all three
+/// components rather than dividing three times.
+pub fn normalize3(x: EFloat32, y: EFloat32, z: EFloat32) -> (EFloat32, EFloat32, EFloat32) {
+    let inv_len = (x.powi(2) + y.powi(2) + z.powi(2)).sqrt().recip();
+    (x * inv_len, y * inv_len, z * inv_len)
+}
+
+/// Reflect the direction `incident` about the surface `normal`, which
+/// should be unit length: `i - 2 (i·n) n`.  The dot product is accumulated
+/// with `dot`, and doubling it is exact (see `scale_pow2`).
+pub fn reflect(incident: &[EFloat32; 3], normal: &[EFloat32; 3]) -> [EFloat32; 3] {
+    let d2 = dot(incident, normal).scale_pow2(1);
+    [
+        incident[0] - d2 * normal[0],
+        incident[1] - d2 * normal[1],
+        incident[2] - d2 * normal[2],
+    ]
+}
+
+/// Refract the unit direction `incident` through a surface with unit
+/// `normal` (facing against `incident`), where `eta` is the ratio of the
+/// refractive indices (incident side over transmitted side), by Snell's law
+/// as in GLSL's `refract`.
+///
+/// Returns `None` for total internal reflection, i.e. when
+/// `k = 1 - eta² (1 - (n·i)²)` is negative.  Close to the critical angle the
+/// interval of `k` may straddle zero; then, as plain floats would, this goes
+/// by the value of `k`, and if that refracts, the negative part of `k`'s
+/// interval is clamped off (a grazing transmitted ray) so the bounds stay
+/// sound.
+pub fn refract(
+    incident: &[EFloat32; 3],
+    normal: &[EFloat32; 3],
+    eta: EFloat32,
+) -> Option<[EFloat32; 3]> {
+    let cos_i = dot(normal, incident);
+    let k = EFloat32::one() - eta.sqr() * cos_i.sqr().one_minus();
+    if k.value() < 0.0 {
+        return None;
+    }
+    let f = eta * cos_i + k.clamp_nonnegative().sqrt();
+    Some([
+        eta * incident[0] - f * normal[0],
+        eta * incident[1] - f * normal[1],
+        eta * incident[2] - f * normal[2],
+    ])
+}
+
+/// The dot product of two slices, accumulated with `mul_add` so that each
+/// term is rounded once instead of twice, which keeps the bounds at least as
+/// tight as multiplying and adding separately.
+///
+/// Panics if the slices differ in length.
+pub fn dot(a: &[EFloat32], b: &[EFloat32]) -> EFloat32 {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "dot product of slices of different lengths"
+    );
+    a.iter()
+        .zip(b.iter())
+        .fold(EFloat32::zero(), |acc, (&x, &y)| x.mul_add(y, acc))
+}
+
+/// `a*b + a*c`, computed as `a*(b + c)`.
+///
+/// Written out the two products are treated as independent, so `a`'s
+/// uncertainty is counted twice and the bounds can be much wider than the
+/// true range.  Factoring it out counts it once.  The sum and product are
+/// also bounded in `f64` and rounded to `f32` just once, rather than widened
+/// by an ulp after each step.
+pub fn mul_add_shared(a: EFloat32, b: EFloat32, c: EFloat32) -> EFloat32 {
+    let bounds = [a.low, a.high, b.low, b.high, c.low, c.high];
+    if bounds.iter().any(|x| !x.is_finite()) {
+        return a * (b + c);
+    }
+    let sum_low = next_f64_down(b.low as f64 + c.low as f64);
+    let sum_high = next_f64_up(b.high as f64 + c.high as f64);
+    let corners = [
+        a.low as f64 * sum_low,
+        a.high as f64 * sum_low,
+        a.low as f64 * sum_high,
+        a.high as f64 * sum_high,
+    ];
+    let low = corners.iter().cloned().fold(f64::INFINITY, f64::min);
+    let high = corners.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let r = EFloat32 {
+        v: a.v * (b.v + c.v),
+        low: f64_to_f32_down(next_f64_down(low)),
+        high: f64_to_f32_up(next_f64_up(high)),
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        precise: a.precise * (b.precise + c.precise),
+        #[cfg(feature = "peak-error")]
+        max_rel_seen: a
+            .peak_relative_error()
+            .max(b.peak_relative_error())
+            .max(c.peak_relative_error()),
+        #[cfg(feature = "initial-error")]
+        initial_error: a.initial_error + b.initial_error + c.initial_error,
+    };
+    r.check();
+    r
+}
+
+/// The determinant `a*d - b*c` of the matrix `[[a, b], [c, d]]`.
+///
+/// Products of two `f32`s are exact in `f64`, so the bounds of both products
+/// are exact and the only rounding is in the final subtraction, rounded
+/// outward once to `f32` (and not at all if the determinant is exact).  That
+/// keeps the bounds tight even for nearly singular matrices where `a*d` and
+/// `b*c` almost cancel, which is exactly when the composed `a * d - b * c`
+/// loses the sign.
+///
+/// The sign of the determinant is only known when the result doesn't
+/// straddle zero (see `sign()`); if it does, the inputs are too uncertain to
+/// tell which way the matrix is oriented, or whether it is singular.
+pub fn det2(a: EFloat32, b: EFloat32, c: EFloat32, d: EFloat32) -> EFloat32 {
+    let bounds = [a.low, a.high, b.low, b.high, c.low, c.high, d.low, d.high];
+    if bounds.iter().any(|x| !x.is_finite()) {
+        return a * d - b * c;
+    }
+    // exact f64 bounds of the product of two intervals
+    let product = |x: EFloat32, y: EFloat32| {
+        let corners = [
+            x.low as f64 * y.low as f64,
+            x.high as f64 * y.low as f64,
+            x.low as f64 * y.high as f64,
+            x.high as f64 * y.high as f64,
+        ];
+        (
+            corners.iter().cloned().fold(f64::INFINITY, f64::min),
+            corners.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        )
+    };
+    let (ad_low, ad_high) = product(a, d);
+    let (bc_low, bc_high) = product(b, c);
+    let (low, err) = two_sum(ad_low, -bc_high);
+    let low = f64_to_f32_down(if err < 0.0 { next_f64_down(low) } else { low });
+    let (high, err) = two_sum(ad_high, -bc_low);
+    let high = f64_to_f32_up(if err > 0.0 { next_f64_up(high) } else { high });
+    let r = EFloat32 {
+        v: (a.v as f64 * d.v as f64 - b.v as f64 * c.v as f64) as f32,
+        low,
+        high,
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        precise: a.precise * d.precise - b.precise * c.precise,
+        #[cfg(feature = "peak-error")]
+        max_rel_seen: a
+            .peak_relative_error()
+            .max(b.peak_relative_error())
+            .max(c.peak_relative_error())
+            .max(d.peak_relative_error()),
+        #[cfg(feature = "initial-error")]
+        initial_error: a.initial_error + b.initial_error + c.initial_error + d.initial_error,
+    };
+    r.check();
+    r
+}
+
+/// The orientation of the triangle `a`, `b`, `c`: the sign of the cross
+/// product `(b - a) × (c - a)`, computed with `det2` (and with differences
+/// that are only widened when inexact).  `Greater` means the
+/// points turn counterclockwise (`c` is left of the line from `a` to `b`),
+/// `Less` clockwise, and `Equal` that they are exactly collinear.
+///
+/// `None` means the points are too close to collinear for their
+/// uncertainty: the determinant's interval straddles zero, so either answer
+/// could be right.
+pub fn orientation(
+    ax: EFloat32,
+    ay: EFloat32,
+    bx: EFloat32,
+    by: EFloat32,
+    cx: EFloat32,
+    cy: EFloat32,
+) -> Option<Ordering> {
+    let det = det2(
+        sub_directed(bx, ax),
+        sub_directed(by, ay),
+        sub_directed(cx, ax),
+        sub_directed(cy, ay),
+    );
+    match det.sign() {
+        IntervalSign::Positive => Some(Ordering::Greater),
+        IntervalSign::Negative => Some(Ordering::Less),
+        IntervalSign::Zero => Some(Ordering::Equal),
+        IntervalSign::Straddling => None,
+    }
+}
+
+/// The linear combination `sum(weight * value)` of `(weight, value)` pairs
+/// with exact weights, the general form behind means, filters and
+/// convolutions.  Each term is accumulated with `mul_add`, so it is rounded
+/// once rather than twice, as in `dot`.
+///
+/// The terms are summed in the order given.  The running sum's error grows
+/// with its magnitude, so ordering the terms from the smallest
+/// `|weight * value|` to the largest keeps the bounds tightest (see also
+/// `scan_errors`).  No terms gives an exact zero.
+pub fn linear_combination(terms: &[(f32, EFloat32)]) -> EFloat32 {
+    terms.iter().fold(EFloat32::zero(), |acc, &(w, x)| {
+        x.mul_add(EFloat32::new(w), acc)
+    })
+}
+
+/// The polar angle of the point `(x, y)`, as `y.atan2(x)`.
+///
+/// When both components are known to have the same sign (the point is in the
+/// first or third quadrant, away from the axes), the angle is monotone in
+/// each component, so only the two extreme corners of the box are evaluated,
+/// and they are rounded directly from f64 to the adjacent f32 (after a step
+/// down or up to cover the error of the f64 `atan2`) instead of by a whole
+/// f32 ulp.  Otherwise this is the same as `atan2`.
+pub fn angle_of(y: EFloat32, x: EFloat32) -> EFloat32 {
+    let (low_corner, high_corner) = if y.low > 0.0 && x.low > 0.0 {
+        // First quadrant: increasing in y, decreasing in x
+        ((y.low, x.high), (y.high, x.low))
+    } else if y.high < 0.0 && x.high < 0.0 {
+        // Third quadrant: decreasing in y, increasing in x
+        ((y.high, x.low), (y.low, x.high))
+    } else {
+        return y.atan2(x);
+    };
+    let angle = |(y, x): (f32, f32)| (y as f64).atan2(x as f64);
+    let r = EFloat32 {
+        v: y.v.atan2(x.v),
+        low: f64_to_f32_down(next_f64_down(angle(low_corner))),
+        high: f64_to_f32_up(next_f64_up(angle(high_corner))),
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        precise: y.precise.atan2(x.precise),
+        #[cfg(feature = "peak-error")]
+        max_rel_seen: y.peak_relative_error().max(x.peak_relative_error()),
+        #[cfg(feature = "initial-error")]
+        initial_error: y.initial_error + x.initial_error,
+    };
+    r.check();
+    r
+}
+
+/// The condition number of the subtraction `a - b`, `(|a| + |b|) / |a - b|`,
+/// computed from the values.  This is how much the relative errors of the
+/// operands may be magnified in the result: near 1 is harmless, and a large
+/// value warns of catastrophic cancellation.  Equal values give infinity.
+pub fn subtraction_condition(a: EFloat32, b: EFloat32) -> f32 {
+    if a.v == b.v {
+        f32::INFINITY
+    } else {
+        (a.v.abs() + b.v.abs()) / (a.v - b.v).abs()
+    }
+}
+
+/// How much an operation amplified the relative error:
+/// `after.relative_error_estimate() / before.relative_error_estimate()`.
+/// Compare this across the steps of a computation to find the ones that hurt
+/// stability the most.
+///
+/// If `before` is exact, there is no ratio, so this is `1.0` if `after` is
+/// exact too, and infinity otherwise.
+pub fn error_growth(before: EFloat32, after: EFloat32) -> f32 {
+    let (b, a) = (
+        before.relative_error_estimate(),
+        after.relative_error_estimate(),
+    );
+    if b == 0.0 {
+        if a == 0.0 {
+            1.0
+        } else {
+            f32::INFINITY
+        }
+    } else {
+        a / b
+    }
+}
+
+/// Of two values computed in different ways for the same quantity, whichever
+/// has the smaller `absolute_error()` (`a` if they are equal).  Both intervals
+/// should contain the true value, so in debug builds this asserts that they
+/// overlap.
+pub fn tighter_of(a: EFloat32, b: EFloat32) -> EFloat32 {
+    debug_assert!(a.overlaps(&b), "{} and {} do not overlap", a, b);
+    if b.absolute_error() < a.absolute_error() {
+        b
+    } else {
+        a
+    }
+}
+
+/// Tighten `a` and `b` using the knowledge that their true values sum to
+/// exactly `target` (e.g. barycentric coordinates summing to 1): `a` can be
+/// no more than `target - b.low` and no less than `target - b.high`, and
+/// likewise for `b`.  Each bound only ever moves inwards, and each value is
+/// clamped into its new bounds.
+///
+/// This is only sound if the invariant really holds for the true values.  If
+/// it doesn't the intervals may end up excluding them, which debug builds
+/// catch in `check()`.
+pub fn constrain_sum(a: &mut EFloat32, b: &mut EFloat32, target: f32) {
+    let (a_low, a_high) = (a.low, a.high);
+    a.low = a.low.max(next_f32_down(target - b.high));
+    a.high = a.high.min(next_f32_up(target - b.low));
+    b.low = b.low.max(next_f32_down(target - a_high));
+    b.high = b.high.min(next_f32_up(target - a_low));
+    for x in [a, b] {
+        x.v = x.v.max(x.low).min(x.high);
+        x.check();
+    }
+}
+
+/// The interval minimum of all the values (see `Float::min`), or `None` if
+/// there are none.  When the intervals overlap it may be unclear which value
+/// is really the smallest, and the result covers every candidate.
+pub fn interval_min<I: IntoIterator<Item = EFloat32>>(iter: I) -> Option<EFloat32> {
+    iter.into_iter().fold(None, |acc, x| match acc {
+        None => Some(x),
+        Some(m) => Some(Float::min(m, x)),
+    })
+}
+
+/// The interval maximum of all the values (see `Float::max`), or `None` if
+/// there are none.
+pub fn interval_max<I: IntoIterator<Item = EFloat32>>(iter: I) -> Option<EFloat32> {
+    iter.into_iter().fold(None, |acc, x| match acc {
+        None => Some(x),
+        Some(m) => Some(Float::max(m, x)),
+    })
+}
+
+/// The step function, as in GLSL: `1` where `x >= edge` and `0` below it.
+///
+/// The result is an exact `1` when `x` is definitely at or above `edge`
+/// (`x.low >= edge.high`), an exact `0` when it is definitely below
+/// (`x.high < edge.low`), and the interval `[0, 1]` when the intervals
+/// overlap and the comparison could go either way.  The value follows the
+/// comparison of the values.
+pub fn step(edge: EFloat32, x: EFloat32) -> EFloat32 {
+    let step_of = |e: f64, x: f64| if x >= e { 1.0 } else { 0.0 };
+    let (low, high) = if x.low >= edge.high {
+        (1.0, 1.0)
+    } else if x.high < edge.low {
+        (0.0, 0.0)
+    } else {
+        (0.0, 1.0)
+    };
+    let r = EFloat32 {
+        v: step_of(edge.v as f64, x.v as f64) as f32,
+        low,
+        high,
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        precise: step_of(edge.precise, x.precise),
+        #[cfg(feature = "peak-error")]
+        max_rel_seen: edge.peak_relative_error().max(x.peak_relative_error()),
+        #[cfg(feature = "initial-error")]
+        initial_error: edge.initial_error + x.initial_error,
+    };
+    r.check();
+    r
+}
+
+/// Hermite interpolation between 0 and 1 as `x` goes from `edge0` to
+/// `edge1`, as in GLSL: `3t^2 - 2t^3` where
+/// `t = clamp((x - edge0) / (edge1 - edge0), 0, 1)`.
+///
+/// The polynomial is increasing on `[0, 1]`, so rather than multiplying
+/// intervals (which would treat each `t` as independent) it is evaluated
+/// once at each clamped bound of `t`.  The result always stays within
+/// `[0, 1]`.
+pub fn smoothstep(edge0: EFloat32, edge1: EFloat32, x: EFloat32) -> EFloat32 {
+    let t = (x - edge0) / (edge1 - edge0);
+    let poly = |t: f64| {
+        let t = t.clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    };
+    // Clamped to 0 or 1 the polynomial is exact, so only round otherwise.
+    let exact = |t: f32| t <= 0.0 || t >= 1.0;
+    let r = EFloat32 {
+        v: poly(t.v as f64) as f32,
+        low: if exact(t.low) {
+            poly(t.low as f64) as f32
+        } else {
+            next_f32_down(poly(t.low as f64) as f32).max(0.0)
+        },
+        high: if exact(t.high) {
+            poly(t.high as f64) as f32
+        } else {
+            next_f32_up(poly(t.high as f64) as f32).min(1.0)
+        },
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        precise: poly(t.precise),
+        #[cfg(feature = "peak-error")]
+        max_rel_seen: t.peak_relative_error(),
+        #[cfg(feature = "initial-error")]
+        initial_error: t.initial_error,
+    };
+    r.check();
+    r
+}
+
+/// Evaluate the piecewise-linear function through the points `(xs[i], ys[i])`
+/// at `x`, e.g. to interpolate a lookup table.  `xs` must be exact and
+/// strictly increasing, and outside its range the function is constant at
+/// the first or last `ys`.
+///
+/// Each segment the interval of `x` reaches is interpolated over just its
+/// part of `x`, and the results are unioned, so when `x` spans a breakpoint
+/// the bounds cover both sides of the kink.  The value comes from the
+/// segment containing `x.value()`.
+///
+/// Panics if `xs` is empty or the lengths differ.
+pub fn eval_piecewise(x: EFloat32, xs: &[f32], ys: &[EFloat32]) -> EFloat32 {
+    assert!(
+        !xs.is_empty() && xs.len() == ys.len(),
+        "piecewise table needs matching, non-empty xs and ys"
+    );
+    debug_assert!(xs.windows(2).all(|w| w[0] < w[1]));
+    let n = xs.len();
+    if n == 1 {
+        return ys[0];
+    }
+    let x = x.clamped(xs[0], xs[n - 1]);
+    let mut low = f32::INFINITY;
+    let mut high = f32::NEG_INFINITY;
+    let mut at_value: Option<EFloat32> = None;
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+    let mut at_precise: Option<EFloat32> = None;
+    for i in 0..n - 1 {
+        let (x0, x1) = (xs[i], xs[i + 1]);
+        if x.high < x0 || x.low > x1 {
+            continue;
+        }
+        let (e0, e1) = (EFloat32::new(x0), EFloat32::new(x1));
+        let t = (x.clamped(x0, x1) - e0) / (e1 - e0);
+        let y = ys[i] + (ys[i + 1] - ys[i]) * t;
+        low = low.min(y.low);
+        high = high.max(y.high);
+        if at_value.is_none() && x0 <= x.v && x.v <= x1 {
+            at_value = Some(y);
+        }
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        {
+            if at_precise.is_none() && x0 as f64 <= x.precise && x.precise <= x1 as f64 {
+                at_precise = Some(y);
+            }
+        }
+    }
+    let y = at_value.unwrap();
+    let r = EFloat32 {
+        v: y.v,
+        low,
+        high,
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        precise: at_precise.unwrap().precise,
+        #[cfg(feature = "peak-error")]
+        max_rel_seen: y.peak_relative_error(),
+        #[cfg(feature = "initial-error")]
+        initial_error: y.initial_error,
+    };
+    r.check();
+    r
+}
+
+/// The largest relative error from rounding a single f32 operation, which is
+/// half of `f32::EPSILON` under round-to-nearest.  See Higham (2002), "Accuracy
+/// and Stability of Numerical Algorithms", sect 3.1.
+pub const MACHINE_EPSILON: f32 = f32::EPSILON * 0.5;
+
+/// Higham's bound on the relative error accumulated over `n` rounded
+/// operations: `n * eps / (1 - n * eps)`, where `eps` is `MACHINE_EPSILON`.
+/// pbrt uses this to bound floating point error without interval arithmetic.
+pub fn gamma(n: i32) -> f32 {
+    (n as f32 * MACHINE_EPSILON) / (1.0 - n as f32 * MACHINE_EPSILON)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+    use proptest::prelude::*;
+
+    // Build a pseudo-random chain of `depth` arithmetic operations from
+    // `seed`, for property tests of the error bounds.  The same seed always
+    // produces the same chain.  Operands are kept within `[0.5, 2.0)` so the
+    // chain neither overflows nor divides by an interval straddling zero.
+    fn random_chain(seed: u64, depth: usize) -> EFloat32 {
+        // splitmix64
+        let mut state = seed;
+        let mut next_operand = || {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+            let v = 0.5 + 1.5 * ((z >> 40) as f32 / (1u64 << 24) as f32);
+            let err = ((z & 0xFF) as f32) * 1e-6;
+            (z, EFloat32::new_with_err(v, err))
+        };
+
+        let (_, mut acc) = next_operand();
+        for _ in 0..depth {
+            let (op, x) = next_operand();
+            acc = match (op >> 32) % 4 {
+                0 => acc + x,
+                1 => acc - x,
+                2 => acc * x,
+                _ => acc / x,
+            };
+        }
+        acc
+    }
+
+    // relative_error() and precise() only exist with precise tracking
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+    #[test]
+    fn test1() {
+        let x = EFloat32::new(0.87234);
+        let y = EFloat32::new(0.2348709);
+        let z = x * y;
+        let w = EFloat32::new(1.0) - z;
+        println!(
+            "value={} upper={} lower={} absolute_error={} relative_error={} precise={}",
+            w.value(),
+            w.upper_bound(),
+            w.lower_bound(),
+            w.absolute_error(),
+            w.relative_error(),
+            w.precise()
+        );
+    }
+
+    #[test]
+    fn test_effective_error() {
+        // at zero, the relative error is meaningless and absolute error is used
+        let zero = EFloat32::new_with_err(0.0, 1e-6);
+        assert_eq!(zero.effective_error(), zero.absolute_error());
+
+        // anywhere else, even at a tiny value, it is the symmetric error
+        let small = EFloat32::new_with_err(1e-7, 1e-6);
+        assert_eq!(small.effective_error(), small.symmetric_error());
+        assert!(small.effective_error() < small.absolute_error());
+        assert!(small.effective_error() >= 1e-6);
+
+        // for large values the one-sided relative estimate is tighter
+        let large = EFloat32::new_with_err(1.0e6, 0.5);
+        assert!(large.effective_error() < large.absolute_error());
+        assert!(large.effective_error() >= 0.5);
+    }
+
+    #[cfg(all(
+        feature = "bytemuck",
+        not(any(debug_assertions, feature = "precise-tracking"))
+    ))]
+    #[test]
+    fn test_bytemuck_cast_slice() {
+        // v, low and high, plus max_rel_seen with the peak-error feature
+        let fields = ::std::mem::size_of::<EFloat32>() / 4;
+        let expected =
+            3 + cfg!(feature = "peak-error") as usize + cfg!(feature = "initial-error") as usize;
+        assert_eq!(fields, expected);
+        let values = [EFloat32::new(1.5), EFloat32::new_with_err(-2.25, 0.125)];
+        let floats: &[f32] = ::bytemuck::cast_slice(&values);
+        assert_eq!(floats.len(), 2 * fields);
+        assert_eq!(floats[fields], -2.25);
+        let bytes: &[u8] = ::bytemuck::cast_slice(&values);
+        let back: &[EFloat32] = ::bytemuck::cast_slice(bytes);
+        for (a, b) in values.iter().zip(back.iter()) {
+            assert_eq!(a.value(), b.value());
+            assert_eq!(a.lower_bound(), b.lower_bound());
+            assert_eq!(a.upper_bound(), b.upper_bound());
+        }
+    }
+
+    #[test]
+    fn test_random_chain_is_deterministic() {
+        let a = random_chain(42, 20);
+        let b = random_chain(42, 20);
+        assert_eq!(a.value(), b.value());
+        assert_eq!(a.lower_bound(), b.lower_bound());
+        assert_eq!(a.upper_bound(), b.upper_bound());
+    }
+
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+    proptest! {
+        #[test]
+        fn test_random_chain_brackets_precise(seed in any::<u64>(), depth in 0usize..64) {
+            prop_assert!(random_chain(seed, depth).check_against_precise());
+        }
+    }
+
+    #[test]
+    fn test_trust_exact() {
+        let x = EFloat32::new_with_err(3.0, 0.25) * EFloat32::new(1.5);
+        assert!(x.absolute_error() > 0.0);
+        let y = x.trust_exact();
+        assert_eq!(y.value(), x.value());
+        assert_eq!(y.lower_bound(), y.value());
+        assert_eq!(y.upper_bound(), y.value());
+        assert_eq!(y.absolute_error(), 0.0);
+    }
+
+    #[test]
+    fn test_exp_m1_ln_1p() {
+        let x = EFloat32::new_with_err(1.0e-6, 1.0e-9);
+
+        let direct = x.ln_1p();
+        let composed = (EFloat32::one() + x).ln();
+        let expected = (1.0e-6f32 as f64).ln_1p();
+        assert!(direct.lower_bound() as f64 <= expected);
+        assert!(expected <= direct.upper_bound() as f64);
+        assert!(composed.lower_bound() <= direct.lower_bound());
+        assert!(composed.upper_bound() >= direct.upper_bound());
+        // forming 1 + x costs at least an ulp of 1.0, swamping the input error
+        assert!(direct.absolute_error() * 10.0 < composed.absolute_error());
+
+        let e = x.exp_m1();
+        assert!(e.lower_bound() < 1.0e-6 && 1.0e-6 < e.upper_bound());
+        assert!(e.absolute_error() < 1.0e-8);
+    }
+
+    #[test]
+    fn test_hyperbolic() {
+        let x = EFloat32::new_with_err(0.5, 1.5);
+        let c = x.cosh();
+        assert_eq!(c.lower_bound(), 1.0);
+        assert!(c.upper_bound() >= 2.0f32.cosh());
+
+        let pos = EFloat32::new_with_err(1.0, 0.1).cosh();
+        assert!(pos.lower_bound() <= 0.9f32.cosh() && 1.1f32.cosh() <= pos.upper_bound());
+        let neg = EFloat32::new_with_err(-1.0, 0.1).cosh();
+        assert!(neg.lower_bound() <= 0.9f32.cosh() && 1.1f32.cosh() <= neg.upper_bound());
+
+        let s = x.sinh();
+        assert!(s.lower_bound() <= (-1.0f32).sinh() && 2.0f32.sinh() <= s.upper_bound());
+        let t = x.tanh();
+        assert!(t.lower_bound() <= (-1.0f32).tanh() && 2.0f32.tanh() <= t.upper_bound());
+    }
+
+    #[test]
+    fn test_abs_sub() {
+        // overlapping intervals: the difference could be negative
+        let a = EFloat32::new_with_err(1.0, 0.5);
+        let b = EFloat32::new_with_err(1.25, 0.5);
+        let d = a.abs_sub(b);
+        assert_eq!(d.lower_bound(), 0.0);
+        assert_eq!(d.value(), 0.0);
+        assert_eq!(d.upper_bound(), (a - b).upper_bound());
+
+        // definitely greater: same as plain subtraction
+        let c = EFloat32::new_with_err(5.0, 0.5);
+        let d = c.abs_sub(a);
+        let plain = c - a;
+        assert_eq!(d.value(), plain.value());
+        assert_eq!(d.lower_bound(), plain.lower_bound());
+        assert_eq!(d.upper_bound(), plain.upper_bound());
+
+        // definitely smaller: exactly zero
+        let d = a.abs_sub(c);
+        assert_eq!(d.lower_bound(), 0.0);
+        assert_eq!(d.upper_bound(), 0.0);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let x = EFloat32::new_with_err(2.5, 0.125) * EFloat32::new(3.0);
+        let snap = x.snapshot();
+        assert_eq!(snap.value, x.value());
+        assert_eq!(snap.low, x.lower_bound());
+        assert_eq!(snap.high, x.upper_bound());
+        assert_eq!(snap.absolute_error, x.absolute_error());
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        assert_eq!(snap.precise, Some(x.precise()));
+        #[cfg(not(any(debug_assertions, feature = "precise-tracking")))]
+        assert_eq!(snap.precise, None);
+    }
+
+    #[test]
+    fn test_mul_scaled() {
+        let a = EFloat32::new_with_err(1.0e20, 1.0e13);
+        let b = EFloat32::new_with_err(1.0e-20, 1.0e-27);
+        let plain = a * b;
+        let scaled = a.mul_scaled(b);
+        assert_eq!(scaled.value(), plain.value());
+        assert!(scaled.lower_bound() >= plain.lower_bound());
+        assert!(scaled.upper_bound() <= plain.upper_bound());
+        assert!(scaled.lower_bound() < 1.0 && 1.0 < scaled.upper_bound());
+
+        // a product landing in the subnormal range still brackets correctly
+        let tiny = EFloat32::new(1.0e-30).mul_scaled(EFloat32::new(1.0e-10));
+        let expected = 1.0e-30f32 as f64 * 1.0e-10f32 as f64;
+        assert!(tiny.lower_bound() as f64 <= expected);
+        assert!(expected <= tiny.upper_bound() as f64);
+    }
+
+    #[test]
+    fn test_parts_round_trip() {
+        let x = EFloat32::new_with_err(0.75, 0.01) * EFloat32::new(3.0);
+        let (v, low, high) = x.into_parts();
+        assert_eq!(v, x.value());
+        assert_eq!(low, x.lower_bound());
+        assert_eq!(high, x.upper_bound());
+        let y = EFloat32::from_parts(v, low, high);
+        assert_eq!(y.into_parts(), (v, low, high));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_parts_rejects_bad_order() {
+        EFloat32::from_parts(1.0, 2.0, 3.0);
+    }
+
+    #[test]
+    fn test_checked_add() {
+        let big = EFloat32::new(f32::MAX * 0.75);
+        assert!(big.checked_add(EFloat32::new(1.0)).is_some());
+        assert!(big.checked_add(big).is_none());
+
+        // the upper bound of MAX is rounded up to infinity
+        let max = EFloat32::new(f32::MAX);
+        assert!(max.checked_add(EFloat32::new(0.0)).is_none());
+
+        // already-infinite inputs are not a new overflow
+        let inf = EFloat32::new_with_err(1.0, f32::INFINITY);
+        assert!(inf.checked_add(EFloat32::new(1.0)).is_some());
+    }
+
+    #[test]
+    fn test_kahan_sum() {
+        let mut terms = vec![EFloat32::new(1.0)];
+        for _ in 0..10000 {
+            terms.push(EFloat32::new(1.0e-8));
+        }
+        let expected = 1.0 + 10000.0 * (1.0e-8f32 as f64);
+
+        let naive: EFloat32 = terms.iter().cloned().sum();
+        let kahan = kahan_sum(terms.iter().cloned());
+
+        // each 1e-8 is under half an ulp of 1.0, so the naive value never moves
+        assert_eq!(naive.value(), 1.0);
+        let naive_err = (naive.value() as f64 - expected).abs();
+        let kahan_err = (kahan.value() as f64 - expected).abs();
+        assert!(kahan_err * 100.0 < naive_err);
+
+        assert!(kahan.lower_bound() as f64 <= expected);
+        assert!(expected <= kahan.upper_bound() as f64);
+        assert!(kahan.absolute_error() < naive.absolute_error());
+    }
+
+    #[test]
+    fn test_recip() {
+        let straddling = EFloat32::new_with_err(0.5, 2.5).recip();
+        assert_eq!(straddling.lower_bound(), -f32::INFINITY);
+        assert_eq!(straddling.upper_bound(), f32::INFINITY);
+
+        let x = EFloat32::new_with_err(2.0, 0.5).recip();
+        assert_eq!(x.value(), 0.5);
+        assert!(x.lower_bound() <= 1.0 / 2.5 && 1.0 / 1.5 <= x.upper_bound());
+
+        let x = EFloat32::new_with_err(-2.0, 0.5).recip();
+        assert!(x.lower_bound() <= 1.0 / -1.5 && 1.0 / -2.5 <= x.upper_bound());
+    }
+
+    #[test]
+    fn test_cap_relative_error() {
+        // wider than the cap: tightened to about 1% either side
+        let wide = EFloat32::new_with_err(100.0, 10.0);
+        let capped = wide.cap_relative_error(0.01);
+        assert!(capped.lower_bound() >= 98.99 && capped.lower_bound() <= 99.0);
+        assert!(capped.upper_bound() <= 101.01 && capped.upper_bound() >= 101.0);
+
+        // narrower than the cap: unchanged
+        let narrow = EFloat32::new_with_err(100.0, 0.001);
+        let capped = narrow.cap_relative_error(0.01);
+        assert_eq!(capped.lower_bound(), narrow.lower_bound());
+        assert_eq!(capped.upper_bound(), narrow.upper_bound());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let x: EFloat32 = "1.25±0.01".parse().unwrap();
+        let expected = EFloat32::new_with_err(1.25, 0.01);
+        assert_eq!(x.value(), 1.25);
+        assert_eq!(x.lower_bound(), expected.lower_bound());
+        assert_eq!(x.upper_bound(), expected.upper_bound());
+
+        let y: EFloat32 = " 1.25 +/- 0.01 ".parse().unwrap();
+        assert_eq!(y.lower_bound(), expected.lower_bound());
+        assert_eq!(y.upper_bound(), expected.upper_bound());
+
+        let exact: EFloat32 = "1.25".parse().unwrap();
+        assert_eq!(exact.lower_bound(), 1.25);
+        assert_eq!(exact.upper_bound(), 1.25);
+
+        assert!("".parse::<EFloat32>().is_err());
+        assert!("abc".parse::<EFloat32>().is_err());
+        assert!("1.25±".parse::<EFloat32>().is_err());
+        assert!("1.25±x".parse::<EFloat32>().is_err());
+        assert!("1.25±-0.01".parse::<EFloat32>().is_err());
+    }
+
+    #[test]
+    fn test_means() {
+        let a = EFloat32::new_with_err(2.0, 0.01);
+        let b = EFloat32::new_with_err(8.0, 0.01);
+        let am = arithmetic_mean(a, b);
+        assert_eq!(am.value(), 5.0);
+        assert!(am.lower_bound() <= 4.99 && 5.01 <= am.upper_bound());
+        let (_, low, high) = (a + b).into_parts();
+        assert_eq!(
+            (am.lower_bound(), am.upper_bound()),
+            (low / 2.0, high / 2.0)
+        );
+        let gm = geometric_mean(a, b);
+        assert_eq!(gm.value(), 4.0);
+        assert!(gm.lower_bound() <= (1.99f32 * 7.99).sqrt());
+        assert!((2.01f32 * 8.01).sqrt() <= gm.upper_bound());
+
+        // no overflow from a + b
+        let big = EFloat32::new(f32::MAX);
+        assert_eq!(arithmetic_mean(big, big).value(), f32::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mul_clamped_rejects_inverted_range() {
+        EFloat32::new(0.5).mul_clamped(EFloat32::new(2.0), 1.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_geometric_mean_rejects_negative() {
+        geometric_mean(EFloat32::new_with_err(0.0, 0.1), EFloat32::new(4.0));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let a = EFloat32::from_parts(1.5, 1.0, 2.0);
+        let touching = EFloat32::from_parts(2.5, 2.0, 3.0);
+        let nested = EFloat32::from_parts(1.5, 1.25, 1.75);
+        let disjoint = EFloat32::from_parts(3.5, 3.0, 4.0);
+        assert!(a.overlaps(&touching) && touching.overlaps(&a));
+        assert!(a.overlaps(&nested) && nested.overlaps(&a));
+        assert!(!a.overlaps(&disjoint) && !disjoint.overlaps(&a));
+    }
+
+    #[test]
+    fn test_compare_f32() {
+        let a = EFloat32::from_parts(1.5, 1.0, 2.0);
+        assert!(a == 1.5 && a != 1.0);
+        assert!(a < 1.75 && a > 1.25 && a <= 1.5 && a >= 1.5);
+        assert!(EFloat32::new(1.0) == 1.0);
+        assert!(EFloat32::nan().partial_cmp(&1.0).is_none());
+
+        // the value comparison says less, but the interval reaches past 1.75
+        assert!(!a.definitely_lt(1.75) && !a.definitely_le(1.75));
+        assert!(a.definitely_lt(2.5) && a.definitely_le(2.0) && !a.definitely_lt(2.0));
+        assert!(!a.definitely_gt(1.25) && !a.definitely_ge(1.25));
+        assert!(a.definitely_gt(0.5) && a.definitely_ge(1.0) && !a.definitely_gt(1.0));
+        assert!(!EFloat32::nan().definitely_lt(1.0) && !EFloat32::nan().definitely_ge(1.0));
+    }
+
+    #[test]
+    fn test_add_error() {
+        let x = EFloat32::new_with_err(10.0, 0.5) * EFloat32::new(0.3);
+        let wider = x.add_error(0.25);
+        assert!(wider.lower_bound() <= x.lower_bound() - 0.25);
+        assert!(wider.upper_bound() >= x.upper_bound() + 0.25);
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        assert!(wider.check_against_precise());
+
+        let rel = x.add_error_relative(0.1);
+        assert!(rel.lower_bound() <= x.lower_bound() - 0.3);
+        assert!(rel.upper_bound() >= x.upper_bound() + 0.3);
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        assert!(rel.check_against_precise());
+    }
+
+    #[test]
+    fn test_sub_checked() {
+        let a = EFloat32::new_with_err(1.0000001, 1.0e-7);
+        let b = EFloat32::new_with_err(1.0, 1.0e-7);
+        let (d, cancelled) = a.sub_checked(b);
+        assert!(cancelled);
+        assert_eq!(d.value(), (a - b).value());
+
+        let c = EFloat32::new_with_err(0.25, 1.0e-7);
+        let (_, cancelled) = a.sub_checked(c);
+        assert!(!cancelled);
+    }
+
+    // EFloat32 as the scalar of generic small-vector code, as vector crates
+    // use their scalar types.  `dot3` exercises Mul and Sum (which starts from
+    // Zero::zero and folds with Add); `length3` adds Float::sqrt; `scale3`
+    // uses NumCast::from to make a constant.  PartialOrd is used for the
+    // comparison at the end.
+    fn dot3<T: Float + Sum>(a: &[T; 3], b: &[T; 3]) -> T {
+        a.iter().zip(b.iter()).map(|(&x, &y)| x * y).sum()
+    }
+
+    fn length3<T: Float + Sum>(a: &[T; 3]) -> T {
+        dot3(a, a).sqrt()
+    }
+
+    fn scale3<T: Float>(a: &[T; 3]) -> [T; 3] {
+        let two = T::from(2.0).unwrap();
+        [a[0] * two, a[1] * two, a[2] * two]
+    }
+
+    #[test]
+    fn test_generic_vector_scalar() {
+        let a = [
+            EFloat32::new_with_err(1.0, 1.0e-6),
+            EFloat32::new_with_err(2.0, 1.0e-6),
+            EFloat32::new_with_err(3.0, 1.0e-6),
+        ];
+        let b = [EFloat32::new(4.0), EFloat32::new(5.0), EFloat32::new(6.0)];
+        let d = dot3(&a, &b);
+        assert_eq!(d.value(), 32.0);
+        assert!(d.lower_bound() < 32.0 && 32.0 < d.upper_bound());
+
+        let len = length3(&a);
+        assert!(len.lower_bound() < 14.0f32.sqrt() && 14.0f32.sqrt() < len.upper_bound());
+        assert!(length3(&scale3(&a)) > len);
+    }
+
+    #[test]
+    fn test_float_trig() {
+        // contains pi/2, so the maximum of sin is reached inside
+        let x = EFloat32::new_with_err(1.5, 0.25);
+        let s = x.sin();
+        assert_eq!(s.upper_bound(), 1.0);
+        assert!(s.lower_bound() <= 1.25f32.sin());
+
+        // contains pi, the minimum of cos
+        let c = EFloat32::new_with_err(3.0, 0.5).cos();
+        assert_eq!(c.lower_bound(), -1.0);
+
+        // wider than a period
+        let w = EFloat32::new_with_err(0.0, 4.0).sin();
+        assert_eq!((w.lower_bound(), w.upper_bound()), (-1.0, 1.0));
+
+        // tan across its asymptote at pi/2
+        let t = x.tan();
+        assert_eq!(t.upper_bound(), f32::INFINITY);
+        let t = EFloat32::new_with_err(0.5, 0.1).tan();
+        assert!(t.lower_bound() <= 0.4f32.tan() && 0.6f32.tan() <= t.upper_bound());
+
+        // atan2 in the first quadrant uses the corners
+        let a = EFloat32::new_with_err(1.0, 0.1).atan2(EFloat32::new_with_err(1.0, 0.1));
+        assert!(a.lower_bound() <= 0.9f32.atan2(1.1) && 1.1f32.atan2(0.9) <= a.upper_bound());
+        // but across the negative x axis it could be anything
+        let a = EFloat32::new_with_err(0.0, 0.1).atan2(EFloat32::new(-1.0));
+        assert!(a.lower_bound() <= -::std::f32::consts::PI);
+        assert!(a.upper_bound() >= ::std::f32::consts::PI);
+    }
+
+    #[test]
+    fn test_float_straddling_zero() {
+        let x = EFloat32::new_with_err(0.5, 1.0);
+
+        let a = x.abs();
+        assert_eq!(a.lower_bound(), 0.0);
+        assert!(a.upper_bound() >= 1.5);
+
+        let sq = x.powi(2);
+        assert!(sq.lower_bound() <= 0.0 && sq.lower_bound() > -1.0e-30);
+        assert!(sq.upper_bound() >= 2.25);
+        let cube = x.powi(3);
+        assert!(cube.lower_bound() <= -0.125 && 3.375 <= cube.upper_bound());
+
+        let f = EFloat32::new_with_err(1.0, 0.25).fract();
+        assert_eq!((f.lower_bound(), f.upper_bound()), (0.0, 1.0));
+        let f = EFloat32::new_with_err(2.5, 0.25).fract();
+        assert!(f.lower_bound() <= 0.25 && 0.75 <= f.upper_bound());
+
+        let h = x.hypot(EFloat32::new(3.0));
+        assert!(h.lower_bound() <= 3.0 && 1.5f32.hypot(3.0) <= h.upper_bound());
+    }
+
+    #[test]
+    fn test_float_misc() {
+        let x = EFloat32::new_with_err(2.0, 0.1);
+        let y = EFloat32::new_with_err(2.1, 0.1);
+        let m = x.max(y);
+        assert_eq!(m.value(), y.value());
+        assert_eq!(m.lower_bound(), y.lower_bound());
+        let m = x.min(y);
+        assert_eq!(m.value(), x.value());
+        assert_eq!(m.upper_bound(), x.upper_bound());
+
+        let p = x.powf(EFloat32::new(0.5));
+        assert!(p.lower_bound() <= 1.9f32.sqrt() && 2.1f32.sqrt() <= p.upper_bound());
+
+        let fma = x.mul_add(y, EFloat32::new(1.0));
+        let separate = x * y + EFloat32::new(1.0);
+        assert!(fma.absolute_error() <= separate.absolute_error());
+        assert!(fma.lower_bound() <= 1.9 * 2.0 + 1.0 && 2.1 * 2.2 + 1.0 <= fma.upper_bound());
+
+        assert_eq!(EFloat32::new_with_err(2.5, 0.25).floor().lower_bound(), 2.0);
+        assert!(EFloat32::nan().is_nan());
+        assert!(EFloat32::infinity().is_infinite());
+    }
+
+    #[test]
+    fn test_floor_exact() {
+        assert_eq!(EFloat32::new_with_err(2.5, 0.25).floor_exact(), Some(2));
+        assert_eq!(EFloat32::new_with_err(-2.5, 0.25).floor_exact(), Some(-3));
+        assert_eq!(EFloat32::new_with_err(3.0, 0.25).floor_exact(), None);
+        assert_eq!(EFloat32::new(f32::MAX).floor_exact(), None);
+    }
+
+    #[test]
+    fn test_error_percent() {
+        let x = EFloat32::from_parts(-4.0, -4.1, -3.9);
+        assert!((x.error_percent() - 5.0).abs() < 1.0e-4);
+        assert_eq!(
+            EFloat32::new_with_err(0.0, 0.1).error_percent(),
+            f32::INFINITY
+        );
+        assert_eq!(EFloat32::new(0.0).error_percent(), 0.0);
+    }
+
+    #[test]
+    fn test_weighted_mean() {
+        let a = EFloat32::new_with_err(2.0, 0.01);
+        let b = EFloat32::new_with_err(3.0, 0.02);
+        let m = weighted_mean(&[(a, 1.0), (b, 1.0)]);
+        let am = arithmetic_mean(a, b);
+        assert_eq!(m.value(), am.value());
+        assert!(m.overlaps(&am));
+
+        let c = EFloat32::new_with_err(7.0, 0.05);
+        let m = weighted_mean(&[(a, 1.0), (b, 2.0), (c, 0.5)]);
+        let expected = (2.0 * 1.0 + 3.0 * 2.0 + 7.0 * 0.5) / 3.5;
+        assert!(m.lower_bound() <= expected && expected <= m.upper_bound());
+        assert!(m.lower_bound() >= a.lower_bound() && m.upper_bound() <= c.upper_bound());
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        assert!(m.check_against_precise());
+
+        let empty = weighted_mean(&[]);
+        assert_eq!(empty.value(), 0.0);
+        assert_eq!(empty.absolute_error(), 0.0);
+    }
+
+    #[cfg(feature = "peak-error")]
+    #[test]
+    fn test_peak_relative_error() {
+        let a = EFloat32::new_with_err(1.0000001, 1.0e-7);
+        let b = EFloat32::new(1.0);
+        let before = a * EFloat32::new(2.0);
+        assert!(before.peak_relative_error() < 1.0e-6);
+
+        // the cancelling subtraction is where precision is lost
+        let cancelled = a - b;
+        assert!(cancelled.relative_error_estimate() > 0.5);
+
+        // adding a large value hides that in the current estimate, but not
+        // in the peak
+        let after = cancelled + EFloat32::new(1000.0);
+        assert!(after.relative_error_estimate() < 1.0e-6);
+        assert!(after.peak_relative_error() >= cancelled.relative_error_estimate());
+    }
+
+    #[cfg(feature = "initial-error")]
+    #[test]
+    fn test_propagated_error() {
+        let a = EFloat32::new_with_err(1.5, 0.01);
+        assert_eq!(a.initial_error(), a.absolute_error());
+        assert_eq!(a.propagated_error(), 0.0);
+
+        // exact inputs contribute nothing, so all of the error is rounding
+        let b = EFloat32::new(1.1) * EFloat32::new(1.3) * EFloat32::new(0.7);
+        assert_eq!(b.initial_error(), 0.0);
+        assert_eq!(b.propagated_error(), b.absolute_error());
+        assert!(b.propagated_error() > 0.0);
+
+        // the multiply chain carries the input's width along and grows it
+        let mut c = a;
+        for _ in 0..4 {
+            c = c * EFloat32::new(1.7);
+        }
+        assert_eq!(c.initial_error(), a.absolute_error());
+        assert!(c.propagated_error() > 0.0);
+        assert!(c.propagated_error() < c.absolute_error());
+
+        let d = a * b;
+        assert_eq!(d.initial_error(), a.absolute_error());
+        assert!(d.absolute_error() > a.absolute_error());
+    }
+
+    #[test]
+    fn test_log_base() {
+        let x = EFloat32::new(8.0).log(EFloat32::new(2.0));
+        assert!(x.lower_bound() <= 3.0 && 3.0 <= x.upper_bound());
+        assert!((x.value() - 3.0).abs() < 1.0e-6);
+        assert!(x.absolute_error() < 1.0e-5);
+
+        // through the trait, as generic code would call it
+        let y = Float::log(EFloat32::new_with_err(100.0, 0.5), EFloat32::new(10.0));
+        assert!(y.lower_bound() <= 99.5f32.log10() && 100.5f32.log10() <= y.upper_bound());
+
+        let straddling = EFloat32::new_with_err(0.5, 1.0).log(EFloat32::new(2.0));
+        assert_eq!(straddling.lower_bound(), f32::NEG_INFINITY);
+        assert_eq!(straddling.upper_bound(), f32::INFINITY);
+
+        assert!(EFloat32::new(-1.0).log(EFloat32::new(2.0)).is_nan());
+        assert!(EFloat32::new(8.0).log(EFloat32::new(-2.0)).is_nan());
+    }
+
+    #[test]
+    fn test_try_div() {
+        let x = EFloat32::new_with_err(3.0, 0.1);
+        let q = x.try_div(EFloat32::new(2.0)).unwrap();
+        assert_eq!(q.value(), 1.5);
+
+        assert_eq!(
+            x.try_div(EFloat32::new(0.0)).unwrap_err(),
+            EFloatError::DivisorIsZero
+        );
+        assert_eq!(
+            x.try_div(EFloat32::new_with_err(0.5, 1.0)).unwrap_err(),
+            EFloatError::DivisorStraddlesZero
+        );
+        assert_eq!(
+            EFloat32::new(f32::MAX)
+                .try_div(EFloat32::new(0.5))
+                .unwrap_err(),
+            EFloatError::NonFinite
+        );
+
+        let r = EFloat32::new(7.0).try_rem(EFloat32::new(4.0)).unwrap();
+        assert_eq!(r.value(), 3.0);
+        assert_eq!(
+            x.try_rem(EFloat32::new(-0.0)).unwrap_err(),
+            EFloatError::DivisorIsZero
+        );
+    }
+
+    #[test]
+    fn test_normalize3() {
+        let (x, y, z) = normalize3(
+            EFloat32::new_with_err(1.0, 1.0e-6),
+            EFloat32::new_with_err(-2.0, 1.0e-6),
+            EFloat32::new_with_err(2.0, 1.0e-6),
+        );
+        assert!(x.lower_bound() < 1.0 / 3.0 && 1.0 / 3.0 < x.upper_bound());
+        assert!(y.lower_bound() < -2.0 / 3.0 && -2.0 / 3.0 < y.upper_bound());
+        assert!(z.lower_bound() < 2.0 / 3.0 && 2.0 / 3.0 < z.upper_bound());
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        assert!(
+            x.check_against_precise() && y.check_against_precise() && z.check_against_precise()
+        );
+
+        let len2 = x.powi(2) + y.powi(2) + z.powi(2);
+        assert!(len2.lower_bound() <= 1.0 && 1.0 <= len2.upper_bound());
+        assert!(len2.absolute_error() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_reflect_refract() {
+        let (x, y, z) = normalize3(
+            EFloat32::new_with_err(1.0, 1.0e-6),
+            EFloat32::new_with_err(-1.0, 1.0e-6),
+            EFloat32::new(0.0),
+        );
+        let incident = [x, y, z];
+        let normal = [EFloat32::new(0.0), EFloat32::new(1.0), EFloat32::new(0.0)];
+
+        let r = reflect(&incident, &normal);
+        let h = 0.5f64.sqrt();
+        for (c, &expected) in r.iter().zip([h, h, 0.0].iter()) {
+            assert!(c.lower_bound() as f64 <= expected && expected <= c.upper_bound() as f64);
+            assert!(c.absolute_error() < 5.0e-5);
+            #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+            assert!(c.check_against_precise());
+        }
+
+        // straight through at normal incidence, whatever the indices
+        let down = [EFloat32::new(0.0), EFloat32::new(-1.0), EFloat32::new(0.0)];
+        let t = refract(&down, &normal, EFloat32::new(1.0 / 1.5)).unwrap();
+        assert!(t[1].lower_bound() <= -1.0 && -1.0 <= t[1].upper_bound());
+        assert!(t[0].lower_bound() <= 0.0 && 0.0 <= t[0].upper_bound());
+
+        // into a denser medium at 45 degrees: sin t = sin i / 1.5
+        let t = refract(
+            &incident,
+            &normal,
+            EFloat32::new_with_err(1.0 / 1.5, 1.0e-7),
+        )
+        .unwrap();
+        let sin_t = h / 1.5;
+        assert!(t[0].lower_bound() as f64 <= sin_t && sin_t <= t[0].upper_bound() as f64);
+        assert!(t[1].upper_bound() < 0.0);
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        assert!(t.iter().all(|c| c.check_against_precise()));
+
+        // out of glass at a grazing angle is totally internally reflected
+        let (x, y, z) = normalize3(EFloat32::new(1.0), EFloat32::new(-0.1), EFloat32::new(0.0));
+        assert!(refract(&[x, y, z], &normal, EFloat32::new(1.5)).is_none());
+    }
+
+    #[test]
+    fn test_snap_to_integer() {
+        let one = EFloat32::new_with_err(3.0, 0.4) * EFloat32::new(1.1);
+        let snapped = one.snap_to_integer().unwrap();
+        assert_eq!(snapped.value(), 3.0);
+        assert_eq!(snapped.absolute_error(), 0.0);
+
+        let none = EFloat32::from_parts(3.5, 3.25, 3.75);
+        assert!(none.snap_to_integer().is_none());
+
+        let two = EFloat32::new_with_err(3.5, 0.75);
+        assert!(two.snap_to_integer().is_none());
+    }
+
+    #[test]
+    fn test_gamma() {
+        assert!(gamma(1) > MACHINE_EPSILON);
+        assert!(gamma(1) < MACHINE_EPSILON * 1.001);
+        for n in 1..100 {
+            assert!(gamma(n + 1) > gamma(n));
+        }
+    }
+
+    #[test]
+    fn test_format_with() {
+        let e = EFloat32::from_parts(2.0, 1.5, 2.5);
+        assert_eq!(e.format_with(ErrorStyle::PlusMinus), "2 ± 0.5");
+        assert_eq!(e.format_with(ErrorStyle::Interval), "[1.5, 2.5]");
+        // 0.5 below 2.0 is 2^22 steps of 2^-23
+        assert_eq!(e.format_with(ErrorStyle::Ulps), "2 (4194304 ulp)");
+        assert_eq!(e.format_with(ErrorStyle::Percent), "2 (25%)");
+        assert_eq!(format!("{}", e), "2 ± 0.5");
+        assert!("2 ± 0.5".parse::<EFloat32>().unwrap().overlaps(&e));
+
+        let exact = EFloat32::new(1.0);
+        assert_eq!(exact.format_with(ErrorStyle::Ulps), "1 (0 ulp)");
+        assert_eq!(exact.format_with(ErrorStyle::Percent), "1 (0%)");
+    }
+
+    #[test]
+    fn test_total_cmp() {
+        let mut values = [
+            EFloat32::new(3.0),
+            EFloat32::nan(),
+            EFloat32::new(-1.0),
+            EFloat32::new_with_err(2.0, 0.5),
+        ];
+        values.sort_by(|a, b| a.total_cmp(b));
+        assert_eq!(values[0].value(), -1.0);
+        assert_eq!(values[1].value(), 2.0);
+        assert_eq!(values[2].value(), 3.0);
+        assert!(values[3].value().is_nan());
+    }
+
+    #[test]
+    fn test_repaired() {
+        let healthy = EFloat32::new_with_err(1.0, 0.1);
+        assert!(!healthy.is_degenerate());
+        assert_eq!(healthy.repaired().into_parts(), healthy.into_parts());
+
+        let mut swapped = healthy;
+        swapped.low = healthy.high;
+        swapped.high = healthy.low;
+        assert!(swapped.is_degenerate());
+        assert_eq!(swapped.repaired().into_parts(), healthy.into_parts());
+
+        let mut nan_low = healthy;
+        nan_low.low = f32::NAN;
+        assert!(nan_low.is_degenerate());
+        let r = nan_low.repaired();
+        assert!(!r.is_degenerate());
+        assert_eq!(r.lower_bound(), f32::NEG_INFINITY);
+        assert_eq!(r.upper_bound(), healthy.upper_bound());
+
+        let mut nan_both = healthy;
+        nan_both.low = f32::NAN;
+        nan_both.high = f32::NAN;
+        assert!(nan_both.is_degenerate());
+        let r = nan_both.repaired();
+        assert!(!r.is_degenerate());
+        assert_eq!(r.lower_bound(), f32::NEG_INFINITY);
+        assert_eq!(r.upper_bound(), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_new_exact() {
+        static TABLE: [EFloat32; 3] = [
+            EFloat32::new_exact(0.5),
+            EFloat32::new_exact(1.0),
+            EFloat32::new_exact(-2.25),
+        ];
+        assert_eq!(TABLE[2].into_parts(), (-2.25, -2.25, -2.25));
+        let sum: EFloat32 = TABLE.iter().cloned().sum();
+        assert_eq!(sum.value(), -0.75);
+        assert!(sum.lower_bound() <= -0.75 && -0.75 <= sum.upper_bound());
+    }
+
+    #[test]
+    fn test_dot() {
+        let a: Vec<EFloat32> = [0.1, 0.2, 0.3, 0.4]
+            .iter()
+            .map(|&x| EFloat32::new(x))
+            .collect();
+        let b: Vec<EFloat32> = [1.0, 3.0, 5.0, 7.0]
+            .iter()
+            .map(|&x| EFloat32::new_with_err(x, 0.001))
+            .collect();
+        let d = dot(&a, &b);
+        let naive: EFloat32 = a.iter().zip(b.iter()).map(|(&x, &y)| x * y).sum();
+        let expected = 0.1 * 1.0 + 0.2 * 3.0 + 0.3 * 5.0 + 0.4 * 7.0;
+        assert!((d.lower_bound() as f64) < expected && expected < d.upper_bound() as f64);
+        assert!(naive.lower_bound() <= d.lower_bound());
+        assert!(d.upper_bound() <= naive.upper_bound());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dot_length_mismatch() {
+        dot(&[EFloat32::one()], &[]);
+    }
+
+    #[test]
+    fn test_significant_bits() {
+        assert_eq!(EFloat32::new(3.0).significant_bits(), 24);
+        assert_eq!(EFloat32::new_with_err(0.1, 0.5).significant_bits(), 0);
+        // 2^-10 relative error, plus a little from outward rounding
+        assert_eq!(
+            EFloat32::new_with_err(1.0, 0.0009765625).significant_bits(),
+            9
+        );
+        assert_eq!(EFloat32::new_with_err(1024.0, 1.0).significant_bits(), 9);
+        assert_eq!(EFloat32::new_with_err(1.0, 0.1).significant_bits(), 3);
+        assert_eq!(EFloat32::new_with_err(3.0, 4.0).significant_bits(), 0);
+        let one_ulp = EFloat32::from_parts(1.0, 1.0, next_f32_up(1.0));
+        assert_eq!(one_ulp.significant_bits(), 23);
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn test_fmod() {
+        // libc only binds the C math functions on a few targets, but fmodf
+        // is always in the C library that std links against.
+        extern "C" {
+            fn fmodf(x: libc::c_float, y: libc::c_float) -> libc::c_float;
+        }
+
+        let cases = [
+            (5.5, 2.0),
+            (-5.5, 2.0),
+            (5.5, -2.0),
+            (-5.5, -2.0),
+            (0.75, 3.0),
+            (-0.75, 3.0),
+        ];
+        for &(x, y) in cases.iter() {
+            let c = unsafe { fmodf(x, y) };
+            let e = EFloat32::new(x).fmod(&EFloat32::new(y));
+            assert_eq!(e.value(), c);
+            assert_eq!(e.value().is_sign_negative(), c.is_sign_negative());
+            assert!(e.lower_bound() <= c && c <= e.upper_bound());
+
+            let e = EFloat32::new_with_err(x, 0.1).fmod(&EFloat32::new_with_err(y, 0.1));
+            assert_eq!(e.value(), c);
+            assert!(e.lower_bound() <= c && c <= e.upper_bound());
+        }
+
+        // Same quotient throughout: tight bounds
+        let e = EFloat32::from_parts(5.5, 5.25, 5.75).fmod(&EFloat32::new(2.0));
+        assert_eq!((e.lower_bound(), e.upper_bound()), (1.25, 1.75));
+
+        // Crossing a multiple of the divisor
+        let e = EFloat32::from_parts(5.5, 5.0, 6.5).fmod(&EFloat32::new(2.0));
+        assert_eq!((e.lower_bound(), e.upper_bound()), (0.0, 2.0));
+        let e = EFloat32::from_parts(-5.5, -6.5, -5.0).fmod(&EFloat32::new(2.0));
+        assert_eq!((e.lower_bound(), e.upper_bound()), (-2.0, 0.0));
+
+        // Divisor including zero
+        let e = EFloat32::new(1.0).fmod(&EFloat32::new_with_err(0.0, 0.1));
+        assert!(e.lower_bound().is_infinite() && e.upper_bound().is_infinite());
+    }
+
+    #[test]
+    fn test_new_with_ulp_err() {
+        let e = EFloat32::new_with_ulp_err(1.0, 2);
+        assert_eq!(ulps_between(e.lower_bound(), 1.0), 2);
+        assert_eq!(ulps_between(1.0, e.upper_bound()), 2);
+        // steps below 1.0 are half the size of those above it
+        assert_eq!(1.0 - e.lower_bound(), f32::EPSILON);
+        assert_eq!(e.upper_bound() - 1.0, 2.0 * f32::EPSILON);
+
+        let e = EFloat32::new_with_ulp_err(-3.0e-40, 5);
+        assert_eq!(ulps_between(e.lower_bound(), e.upper_bound()), 10);
+
+        let e = EFloat32::new_with_ulp_err(7.0, 0);
+        assert_eq!(e.into_parts(), (7.0, 7.0, 7.0));
+
+        // crossing zero takes one step, and huge counts saturate at infinity
+        let e = EFloat32::new_with_ulp_err(0.0, 1);
+        assert_eq!(e.into_parts(), (0.0, -1.0e-45, 1.0e-45));
+        let e = EFloat32::new_with_ulp_err(1.0, u32::MAX);
+        assert_eq!(e.lower_bound(), f32::NEG_INFINITY);
+        assert_eq!(e.upper_bound(), f32::INFINITY);
+        let e = EFloat32::new_with_ulp_err(f32::MAX, 1);
+        assert_eq!(e.upper_bound(), f32::INFINITY);
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn test_to_f16_interval() {
+        // exactly representable in f16
+        let e = EFloat32::from_parts(1.5, 1.25, 1.75);
+        let (low, high) = e.to_f16_interval();
+        assert_eq!((low.to_f32(), high.to_f32()), (1.25, 1.75));
+
+        // not representable, so the f16 interval is wider
+        let e = EFloat32::new_with_err(0.1, 0.0001);
+        let (low, high) = e.to_f16_interval();
+        assert!(low.to_f32() < e.lower_bound() && e.upper_bound() < high.to_f32());
+        let back = EFloat32::from_parts(e.value(), low.to_f32(), high.to_f32());
+        assert!(back.absolute_error() < e.absolute_error() + 0.0002);
+
+        // beyond the range of f16
+        let e = EFloat32::from_parts(1.0e6, 1.0e6, 1.0e6);
+        let (low, high) = e.to_f16_interval();
+        assert_eq!(low, f16::MAX);
+        assert_eq!(high, f16::INFINITY);
+        let (low, high) = (-e).to_f16_interval();
+        assert_eq!(low, f16::NEG_INFINITY);
+        assert_eq!(high, f16::MIN);
+    }
+
+    #[test]
+    fn test_as_range() {
+        let e = EFloat32::new_with_err(2.0, 0.5);
+        let range = e.as_range();
+        assert_eq!(*range.start(), e.lower_bound());
+        assert_eq!(*range.end(), e.upper_bound());
+        assert!(range.contains(&2.5));
+        assert!(!range.contains(&2.6));
+    }
+
+    #[test]
+    fn test_angle_of() {
+        for &(y, x) in [(3.0f32, 4.0f32), (3.0, -4.0), (-3.0, -4.0), (-3.0, 4.0)].iter() {
+            let ey = EFloat32::new_with_err(y, 0.001);
+            let ex = EFloat32::new_with_err(x, 0.001);
+            let a = angle_of(ey, ex);
+            let b = ey.atan2(ex);
+            let expected = (y as f64).atan2(x as f64);
+            assert!((a.lower_bound() as f64) < expected && expected < a.upper_bound() as f64);
+            assert!(b.lower_bound() <= a.lower_bound() && a.upper_bound() <= b.upper_bound());
+            assert!(a.absolute_error() < 0.001);
+        }
+
+        // Same-sign quadrants are tighter than atan2 for an exact point
+        let a = angle_of(EFloat32::new(1.0), EFloat32::new(2.0));
+        let b = EFloat32::new(1.0).atan2(EFloat32::new(2.0));
+        assert!(a.absolute_error() < b.absolute_error());
+        let a = angle_of(EFloat32::new(-1.0), EFloat32::new(-2.0));
+        let b = EFloat32::new(-1.0).atan2(EFloat32::new(-2.0));
+        assert!(a.absolute_error() < b.absolute_error());
+    }
+
+    #[test]
+    fn test_margins() {
+        let e = EFloat32::new_with_err(1.0, 0.1);
+        assert!(e.lower_margin() > 0.1 && e.upper_margin() > 0.1);
+        assert_ne!(e.lower_margin(), e.upper_margin());
+        assert!((e.upper_margin() - e.lower_margin()).abs() < 2.0 * f32::EPSILON);
+        assert_eq!(e.lower_margin() + e.upper_margin(), e.absolute_error());
+
+        assert_eq!(e.symmetric_error(), e.lower_margin().max(e.upper_margin()));
+
+        let e = EFloat32::from_parts(2.0, 1.5, 3.0);
+        assert_eq!((e.lower_margin(), e.upper_margin()), (0.5, 1.0));
+        assert_eq!(e.symmetric_error(), 1.0);
+        assert!(e.symmetric_error() > e.absolute_error() / 2.0);
+        assert_eq!(EFloat32::from_parts(2.0, 0.5, 2.25).symmetric_error(), 1.5);
+    }
+
+    #[test]
+    fn test_from_i64() {
+        let e = EFloat32::from_i64_checked(1 << 24).unwrap();
+        assert_eq!(e.into_parts(), (16777216.0, 16777216.0, 16777216.0));
+        assert!(EFloat32::from_i64_checked((1 << 24) + 1).is_none());
+        assert!(EFloat32::from_i64_checked((1 << 24) + 2).is_some());
+        assert!(EFloat32::from_i64_checked(-(1 << 24) - 1).is_none());
+        assert!(EFloat32::from_i64_checked(i64::MAX).is_none());
+        assert!(EFloat32::from_i64_checked(i64::MIN).is_some());
+
+        assert_eq!(
+            EFloat32::from_i64(1 << 24).into_parts(),
+            (16777216.0, 16777216.0, 16777216.0)
+        );
+        let e = EFloat32::from_i64((1 << 24) + 1);
+        assert_eq!((e.lower_bound(), e.upper_bound()), (16777216.0, 16777218.0));
+        let e = EFloat32::from_i64(-(1 << 24) - 1);
+        assert_eq!(
+            (e.lower_bound(), e.upper_bound()),
+            (-16777218.0, -16777216.0)
+        );
+    }
+
+    #[test]
+    fn test_sub_correlated() {
+        let x = EFloat32::new_with_err(3.0, 0.25);
+        let naive = x - x;
+        assert!(naive.lower_bound() < 0.0 && naive.upper_bound() > 0.0);
+
+        let d = x.sub_correlated(x, 1.0);
+        assert_eq!(d.into_parts(), (0.0, 0.0, 0.0));
+
+        let d = x.sub_correlated(x, 0.0);
+        assert_eq!(d.into_parts(), naive.into_parts());
+
+        let d = x.sub_correlated(x, 0.5);
+        assert!(naive.lower_bound() < d.lower_bound() && d.lower_bound() < 0.0);
+        assert!(0.0 < d.upper_bound() && d.upper_bound() < naive.upper_bound());
+
+        // shifted by an exact amount, the error is the same
+        let y = x + EFloat32::new(1.0);
+        let d = y.sub_correlated(x, 1.0);
+        assert!(d.lower_bound() <= 1.0 && 1.0 <= d.upper_bound());
+        assert!(d.absolute_error() < (y - x).absolute_error());
+    }
+
+    #[test]
+    fn test_array_round_trip() {
+        let e = EFloat32::new_with_err(1.5, 0.125).sqrt();
+        let a = e.to_array();
+        assert_eq!(a, [e.value(), e.lower_bound(), e.upper_bound()]);
+        assert_eq!(EFloat32::from_array(a).to_array(), a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_array_out_of_order() {
+        EFloat32::from_array([1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_nth_root() {
+        let e = EFloat32::new_with_err(16.0, 0.5);
+        let r = e.nth_root(2);
+        assert!(r.lower_bound() <= e.sqrt().lower_bound());
+        assert!(r.lower_bound() < 15.5f32.sqrt() && 16.5f32.sqrt() < r.upper_bound());
+
+        let e = EFloat32::from_parts(-8.0, -27.0, -1.0);
+        let r = e.nth_root(3);
+        assert_eq!(r.value(), -2.0);
+        assert!(r.lower_bound() < -3.0 && -3.0 - r.lower_bound() < 1e-6);
+        assert!(r.upper_bound() > -1.0 && r.upper_bound() + 1.0 < 1e-6);
+
+        let e = EFloat32::from_parts(-16.0, -81.0, -1.0);
+        let r = e.nth_root(4);
+        assert!(r.value().is_nan() && r.lower_bound().is_nan() && r.upper_bound().is_nan());
+
+        let e = EFloat32::from_parts(1.0, -1.0, 81.0);
+        let r = e.nth_root(4);
+        assert_eq!(r.lower_bound(), 0.0);
+        assert!(r.upper_bound() > 3.0 && r.upper_bound() - 3.0 < 1e-6);
+
+        // a negative value in a straddling interval roots to zero, and the
+        // result stays usable
+        let e = EFloat32::from_parts(-0.5, -1.5, 0.5);
+        for &n in [2, 4].iter() {
+            let r = e.nth_root(n);
+            assert_eq!((r.value(), r.lower_bound()), (0.0, 0.0));
+            assert!(r.upper_bound() >= 0.5f32.powf(1.0 / n as f32));
+            let s = r + EFloat32::new(1.0);
+            assert!(s.lower_bound() <= 1.0 && s.value() == 1.0);
+        }
+
+        let e = EFloat32::from_parts(-32.0, -32.0, 32.0);
+        let r = e.nth_root(5);
+        assert!(r.lower_bound() < -2.0 && r.upper_bound() > 2.0);
+        assert!(r.absolute_error() < 4.0001);
+    }
+
+    #[test]
+    fn test_clamp_nonnegative() {
+        let e = EFloat32::from_parts(-0.001, -0.002, 0.5).clamp_nonnegative();
+        assert_eq!(e.into_parts(), (0.0, 0.0, 0.5));
+
+        let e = EFloat32::from_parts(0.25, -0.002, 0.5).clamp_nonnegative();
+        assert_eq!(e.into_parts(), (0.25, 0.0, 0.5));
+
+        let e = EFloat32::from_parts(-1.0, -2.0, -0.5).clamp_nonnegative();
+        assert_eq!(e.into_parts(), (0.0, 0.0, 0.0));
+
+        let e = EFloat32::from_parts(1.0, 0.5, 2.0).clamp_nonnegative();
+        assert_eq!(e.into_parts(), (1.0, 0.5, 2.0));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_samples_within_bounds() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let values = [
+            EFloat32::from_parts(1.0, 0.5, 3.0),
+            EFloat32::from_parts(0.5, 0.5, 3.0),
+            EFloat32::from_parts(-2.0, -2.5, -2.0),
+            EFloat32::new_with_err(1e-30, 1e-35),
+            EFloat32::new(4.0),
+        ];
+        for e in values.iter() {
+            let mut below = 0;
+            for _ in 0..1000 {
+                let u = e.sample_uniform(&mut rng);
+                let t = e.sample_triangular(&mut rng);
+                assert!(e.lower_bound() <= u && u <= e.upper_bound());
+                assert!(e.lower_bound() <= t && t <= e.upper_bound());
+                if t < e.value() {
+                    below += 1;
+                }
+            }
+            if e.lower_bound() == e.value() {
+                assert_eq!(below, 0);
+            }
+        }
+        assert_eq!(EFloat32::new(4.0).sample_uniform(&mut rng), 4.0);
+    }
+
+    #[test]
+    fn test_mul_add_accumulator() {
+        let m = |i: usize, j: usize, err: f32| {
+            EFloat32::new_with_err(0.1 * (i * 3 + j) as f32 - 0.35, err)
+        };
+        for i in 0..3 {
+            for j in 0..3 {
+                let mut acc = MulAddAccumulator::new();
+                let mut naive = EFloat32::zero();
+                let mut exact = 0.0f64;
+                for k in 0..3 {
+                    let (a, b) = (m(i, k, 1e-4), m(k, j, 0.0));
+                    acc.accumulate(a, b);
+                    naive = naive + a * b;
+                    exact += a.value() as f64 * b.value() as f64;
+                }
+                let c = acc.into_efloat();
+                assert!((c.lower_bound() as f64) <= exact && exact <= c.upper_bound() as f64);
+                assert!(naive.lower_bound() <= c.lower_bound());
+                assert!(c.upper_bound() <= naive.upper_bound());
+                assert!(c.absolute_error() < naive.absolute_error());
+            }
+        }
+    }
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(
+            EFloat32::new_with_err(1.0, 0.5).sign(),
+            IntervalSign::Positive
+        );
+        assert_eq!(
+            EFloat32::new_with_err(-1.0, 0.5).sign(),
+            IntervalSign::Negative
+        );
+        assert_eq!(EFloat32::zero().sign(), IntervalSign::Zero);
+        assert_eq!(EFloat32::new(-0.0).sign(), IntervalSign::Zero);
+        assert_eq!(
+            EFloat32::new_with_err(0.25, 0.5).sign(),
+            IntervalSign::Straddling
+        );
+        assert_eq!(
+            EFloat32::from_parts(0.5, 0.0, 1.0).sign(),
+            IntervalSign::Straddling
+        );
+        assert_eq!(EFloat32::nan().sign(), IntervalSign::Straddling);
+    }
+
+    #[cfg(feature = "num-rational")]
+    #[test]
+    fn test_from_ratio() {
+        let third = EFloat32::from_ratio(Ratio::new(1, 3));
+        assert_eq!(third.value(), 1.0 / 3.0);
+        assert_eq!(next_f32_up(third.lower_bound()), third.upper_bound());
+        assert!((third.lower_bound() as f64) < 1.0 / 3.0);
+        assert!(1.0 / 3.0 < (third.upper_bound() as f64));
+
+        let e = EFloat32::from_ratio(Ratio::new(-2, 3));
+        assert_eq!(next_f32_up(e.lower_bound()), e.upper_bound());
+        assert!((e.lower_bound() as f64) < -2.0 / 3.0 && -2.0 / 3.0 < (e.upper_bound() as f64));
+
+        let e = EFloat32::from_ratio(Ratio::new(3, 4));
+        assert_eq!(e.into_parts(), (0.75, 0.75, 0.75));
+
+        // too large for f64 to hold exactly
+        let e = EFloat32::from_ratio(Ratio::new(i64::MAX, 3));
+        let exact = i64::MAX as f64 / 3.0;
+        assert!((e.lower_bound() as f64) < exact && exact < (e.upper_bound() as f64));
+    }
+
+    #[test]
+    fn test_subtraction_condition() {
+        let c = subtraction_condition(EFloat32::new(3.0), EFloat32::new(-1.0));
+        assert_eq!(c, 1.0);
+        let c = subtraction_condition(EFloat32::new(1.0), EFloat32::new(0.5));
+        assert_eq!(c, 3.0);
+        let c = subtraction_condition(EFloat32::new(1.0001), EFloat32::new(1.0));
+        assert!(c > 10000.0);
+        let c = subtraction_condition(EFloat32::new(2.0), EFloat32::new_with_err(2.0, 0.1));
+        assert!(c.is_infinite());
+    }
+
+    #[test]
+    fn test_error_growth() {
+        let x = EFloat32::new_with_err(1.001, 1.0e-6);
+
+        // cancellation amplifies the relative error enormously
+        let diff = x - EFloat32::new(1.0);
+        assert!(error_growth(x, diff) > 500.0);
+
+        // multiplication barely changes it
+        let prod = x * EFloat32::new(3.7);
+        let g = error_growth(x, prod);
+        assert!(g > 0.9 && g < 1.1, "{}", g);
+
+        assert_eq!(error_growth(EFloat32::new(2.0), EFloat32::new(4.0)), 1.0);
+        let inexact = EFloat32::new(1.1) * EFloat32::new(1.3);
+        assert!(error_growth(EFloat32::new(1.1), inexact).is_infinite());
+    }
+
+    #[test]
+    fn test_mul_same_sign() {
+        // the four-corner bounds, as the general path computes them
+        let corners = |a: EFloat32, b: EFloat32| {
+            let p = [
+                a.lower_bound() * b.lower_bound(),
+                a.upper_bound() * b.lower_bound(),
+                a.lower_bound() * b.upper_bound(),
+                a.upper_bound() * b.upper_bound(),
+            ];
+            (
+                next_f32_down(p.iter().cloned().fold(f32::INFINITY, f32::min)),
+                next_f32_up(p.iter().cloned().fold(f32::NEG_INFINITY, f32::max)),
+            )
+        };
+        let values = [
+            EFloat32::new_with_err(1.7, 0.01),
+            EFloat32::new_with_err(0.3, 0.2),
+            EFloat32::new_with_err(-2.5, 0.5),
+            EFloat32::new_with_err(-0.001, 1.0e-5),
+            EFloat32::new_with_err(0.1, 0.5),
+            EFloat32::from_parts(2.0, 1.0, f32::INFINITY),
+        ];
+        for &a in values.iter() {
+            for &b in values.iter() {
+                let p = a * b;
+                assert_eq!((p.lower_bound(), p.upper_bound()), corners(a, b));
+            }
+        }
+
+        // mixed signs still take the corners
+        let p = values[0] * values[2];
+        assert!(p.upper_bound() < 0.0);
+        assert_eq!(
+            (p.lower_bound(), p.upper_bound()),
+            corners(values[0], values[2])
+        );
+    }
+
+    #[test]
+    fn test_scan_errors() {
+        let values: Vec<EFloat32> = (1..50).map(|i| EFloat32::new(1.0 / i as f32)).collect();
+        let scanned: Vec<(EFloat32, f32)> = scan_errors(values.iter().cloned()).collect();
+        assert_eq!(scanned.len(), values.len());
+        for w in scanned.windows(2) {
+            assert!(w[1].1 >= w[0].1);
+        }
+        let (last, err) = scanned[scanned.len() - 1];
+        assert_eq!(
+            last.into_parts(),
+            values.iter().cloned().sum::<EFloat32>().into_parts()
+        );
+        assert_eq!(err, last.absolute_error());
+        assert!(err > 0.0);
+
+        assert_eq!(scan_errors(Vec::new()).count(), 0);
+    }
+
+    #[test]
+    fn test_is_exact_zero() {
+        assert!(EFloat32::new(0.0).is_exact_zero() && EFloat32::new(0.0).is_zero());
+        assert!(EFloat32::new(-0.0).is_exact_zero() && EFloat32::new(-0.0).is_zero());
+        assert!(EFloat32::from_parts(0.0, -0.0, 0.0).is_exact_zero());
+
+        let straddling = EFloat32::new_with_err(0.0, 1.0e-6);
+        assert!(straddling.is_zero());
+        assert!(!straddling.is_exact_zero());
+        assert!(!EFloat32::new(1.0e-30).is_exact_zero());
+    }
+
+    #[test]
+    fn test_mul_clamped() {
+        let color = EFloat32::new_with_err(0.8, 0.01);
+
+        // entirely over the top collapses to exactly 1
+        let bright = color.mul_clamped(EFloat32::new(1.5), 0.0, 1.0);
+        assert_eq!(bright.into_parts(), (1.0, 1.0, 1.0));
+
+        // reaching past the top only clips the upper bound
+        let edge = color.mul_clamped(EFloat32::new_with_err(1.24, 0.02), 0.0, 1.0);
+        assert_eq!(edge.value(), 0.8 * 1.24);
+        assert_eq!(edge.upper_bound(), 1.0);
+        assert!(edge.lower_bound() < 0.97);
+
+        // inside the range it is an ordinary product
+        let dim = color.mul_clamped(EFloat32::new(0.5), 0.0, 1.0);
+        assert_eq!(dim.into_parts(), (color * EFloat32::new(0.5)).into_parts());
+
+        let dark = color.mul_clamped(EFloat32::new(-1.0), 0.0, 1.0);
+        assert!(dark.is_exact_zero());
+    }
+
+    #[test]
+    fn test_representable_count() {
+        assert_eq!(EFloat32::new(1.5).representable_count(), 1);
+        assert_eq!(EFloat32::new(-0.0).representable_count(), 1);
+        let one_ulp = EFloat32::from_parts(1.0, 1.0, next_f32_up(1.0));
+        assert_eq!(one_ulp.representable_count(), 2);
+        assert_eq!(EFloat32::new_with_ulp_err(-3.0, 2).representable_count(), 5);
+
+        // across zero: the smallest subnormals on either side, and one zero
+        let tiny = f32::from_bits(1);
+        assert_eq!(
+            EFloat32::from_parts(0.0, -tiny, tiny).representable_count(),
+            3
+        );
+        assert_eq!(
+            EFloat32::from_parts(0.0, -0.0, tiny).representable_count(),
+            2
+        );
+        assert_eq!(
+            EFloat32::from_parts(0.0, -1.0, 1.0).representable_count(),
+            2 * (1.0f32.to_bits() as u64) + 1
+        );
+    }
+
+    #[test]
+    fn test_map_monotone() {
+        let x = EFloat32::new_with_err(2.0, 0.1);
+
+        // a logistic curve, which the crate doesn't provide
+        let logistic = |t: f32| 1.0 / (1.0 + (-t).exp());
+        let y = x.map_monotone_increasing(logistic);
+        assert_eq!(y.value(), logistic(2.0));
+        assert!(y.lower_bound() < logistic(1.9) && logistic(2.1) < y.upper_bound());
+        assert!(y.absolute_error() < 0.03);
+
+        // and a decreasing one
+        let decay = |t: f32| (-0.5 * t).exp();
+        let z = x.map_monotone_decreasing(decay);
+        assert_eq!(z.value(), decay(2.0));
+        assert!(z.lower_bound() < decay(2.1) && decay(1.9) < z.upper_bound());
+        assert!(z.lower_bound() <= z.value() && z.value() <= z.upper_bound());
     }
 
-    fn to_i32(&self) -> Option<i32> {
-        self.v.to_i32()
+    #[test]
+    fn test_new_from_rounded() {
+        let (up, down) = (next_f32_up(1.5), next_f32_down(1.5));
+        let parts = |v: f32, mode: RoundingMode| {
+            let e = EFloat32::new_from_rounded(v, mode);
+            (e.lower_bound(), e.upper_bound())
+        };
+        assert_eq!(parts(1.5, RoundingMode::Nearest), (down, up));
+        assert_eq!(parts(1.5, RoundingMode::Down), (1.5, up));
+        assert_eq!(parts(1.5, RoundingMode::Up), (down, 1.5));
+        assert_eq!(parts(1.5, RoundingMode::TowardZero), (1.5, up));
+        assert_eq!(parts(-1.5, RoundingMode::TowardZero), (-up, -1.5));
+
+        // 1/3 rounded to nearest (up, as it happens) is bracketed either way
+        let third = EFloat32::new_from_rounded(1.0 / 3.0, RoundingMode::Nearest);
+        assert!(third.lower_bound() as f64 <= 1.0 / 3.0 && 1.0 / 3.0 <= third.upper_bound() as f64);
+        assert_eq!(third.value(), 1.0 / 3.0);
     }
 
-    fn to_usize(&self) -> Option<usize> {
-        self.v.to_usize()
+    #[test]
+    fn test_clamp01() {
+        let below = EFloat32::new_with_err(-0.5, 0.1).clamp01();
+        assert_eq!(below.into_parts(), (0.0, 0.0, 0.0));
+        let above = EFloat32::new_with_err(1.5, 0.1).clamp01();
+        assert_eq!(above.into_parts(), (1.0, 1.0, 1.0));
+
+        let low_edge = EFloat32::new_with_err(0.05, 0.1).clamp01();
+        assert_eq!(low_edge.lower_bound(), 0.0);
+        assert_eq!(low_edge.value(), 0.05);
+        assert!(low_edge.upper_bound() >= 0.15);
+        let high_edge = EFloat32::new_with_err(0.95, 0.1).clamp01();
+        assert_eq!(high_edge.upper_bound(), 1.0);
+        assert!(high_edge.lower_bound() <= 0.85);
+
+        let inside = EFloat32::new_with_err(0.5, 0.1);
+        assert_eq!(inside.clamp01().into_parts(), inside.into_parts());
     }
 
-    fn to_u8(&self) -> Option<u8> {
-        self.v.to_u8()
+    #[test]
+    fn test_is_accurate_to_decimals() {
+        let x = EFloat32::new_with_err(1234.5, 0.05);
+        assert!(x.is_accurate_to_decimals(3) && x.is_accurate_to_decimals(4));
+        assert!(!x.is_accurate_to_decimals(5));
+
+        let y = EFloat32::new_with_err(0.001, 2.0e-9);
+        assert!(y.is_accurate_to_decimals(5) && !y.is_accurate_to_decimals(6));
+
+        assert!(EFloat32::new(0.1).is_accurate_to_decimals(30));
+        assert!(EFloat32::new(0.0).is_accurate_to_decimals(30));
+        assert!(!EFloat32::new_with_err(0.0, 1.0e-20).is_accurate_to_decimals(0));
     }
 
-    fn to_u16(&self) -> Option<u16> {
-        self.v.to_u16()
+    #[test]
+    fn test_mul_add_shared() {
+        let a = EFloat32::new_with_err(2.0, 0.1);
+        let b = EFloat32::new_with_err(3.0, 0.1);
+        let c = EFloat32::new_with_err(-2.9, 0.1);
+        let shared = mul_add_shared(a, b, c);
+        let expanded = a * b + a * c;
+        assert!(shared.lower_bound() > expanded.lower_bound() + 0.4);
+        assert!(shared.upper_bound() < expanded.upper_bound() - 0.4);
+        assert!(shared.absolute_error() <= (a * (b + c)).absolute_error());
+        assert!(shared.lower_bound() <= 1.9 * -0.1 && 2.1 * 0.3 <= shared.upper_bound());
+        assert!((shared.value() - 0.2).abs() < 1.0e-5);
+
+        let exact = mul_add_shared(EFloat32::new(3.0), EFloat32::new(0.5), EFloat32::new(0.25));
+        assert_eq!(exact.value(), 2.25);
+        assert!(exact.lower_bound() <= 2.25 && 2.25 <= exact.upper_bound());
+
+        let wide = mul_add_shared(a, EFloat32::from_parts(1.0, 0.0, f32::INFINITY), c);
+        assert_eq!(wide.upper_bound(), f32::INFINITY);
     }
 
-    fn to_u32(&self) -> Option<u32> {
-        self.v.to_u32()
+    #[test]
+    fn test_to_probability() {
+        // 1 - 0.3 - 0.7 can land just below zero
+        let p = EFloat32::new(1.0) - EFloat32::new(0.3) - EFloat32::new(0.7);
+        assert!(p.lower_bound() < 0.0);
+        let clamped = p.to_probability().unwrap();
+        assert!(clamped.lower_bound() == 0.0 && clamped.value() >= 0.0);
+
+        let q = EFloat32::new_with_err(1.0, 1.0e-6)
+            .to_probability()
+            .unwrap();
+        assert_eq!(q.upper_bound(), 1.0);
+        assert_eq!(
+            EFloat32::new_with_err(0.4, 0.1)
+                .to_probability()
+                .unwrap()
+                .into_parts(),
+            EFloat32::new_with_err(0.4, 0.1).into_parts()
+        );
+
+        assert_eq!(
+            EFloat32::new_with_err(-0.2, 0.1)
+                .to_probability()
+                .unwrap_err(),
+            ProbabilityError::BelowZero
+        );
+        assert_eq!(
+            EFloat32::new(1.001).to_probability().unwrap_err(),
+            ProbabilityError::AboveOne
+        );
+        assert_eq!(
+            EFloat32::nan().to_probability().unwrap_err(),
+            ProbabilityError::NaN
+        );
     }
 
-    fn to_f32(&self) -> Option<f32> {
-        self.v.to_f32()
+    #[test]
+    fn test_from_samples() {
+        let e = EFloat32::from_samples(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(e.value(), 3.0);
+        // s = sqrt(2.5), so the standard error is sqrt(0.5)
+        let sem = 0.5f32.sqrt();
+        assert!((e.upper_bound() - (3.0 + sem)).abs() < 1.0e-6);
+        assert!((e.lower_bound() - (3.0 - sem)).abs() < 1.0e-6);
+
+        let same = EFloat32::from_samples(&[0.5, 0.5, 0.5]);
+        assert_eq!(same.into_parts(), (0.5, 0.5, 0.5));
+
+        assert_eq!(
+            EFloat32::from_samples(&[7.25]).into_parts(),
+            (7.25, 7.25, 7.25)
+        );
+        assert!(EFloat32::from_samples(&[]).value().is_nan());
     }
 
-    fn to_f64(&self) -> Option<f64> {
-        self.v.to_f64()
+    #[test]
+    fn test_det2() {
+        assert_eq!(
+            det2(
+                EFloat32::new(3.0),
+                EFloat32::new(2.0),
+                EFloat32::new(1.0),
+                EFloat32::new(4.0)
+            )
+            .value(),
+            10.0
+        );
+
+        // Nearly singular: ad = 1 + 2^-24 - 2^-47 rounds to 1 in f32, so the
+        // composed form can't tell the sign, but it is positive.
+        let (a, d) = (
+            EFloat32::new(next_f32_up(1.0)),
+            EFloat32::new(next_f32_down(1.0)),
+        );
+        let one = EFloat32::new(1.0);
+        let naive = a * d - one * one;
+        assert_ne!(naive.sign(), IntervalSign::Positive);
+        let det = det2(a, one, one, d);
+        assert_eq!(det.sign(), IntervalSign::Positive);
+        let expected = 2.0f64.powi(-24) - 2.0f64.powi(-47);
+        assert!(det.lower_bound() as f64 <= expected && expected <= det.upper_bound() as f64);
+        assert!(det.absolute_error() < 1.0e-12);
+
+        // with real uncertainty in the entries, the sign is unknown
+        let fuzzy = det2(EFloat32::new_with_err(1.0, 1.0e-6), one, one, d);
+        assert_eq!(fuzzy.sign(), IntervalSign::Straddling);
+        assert!(
+            fuzzy.absolute_error()
+                <= (EFloat32::new_with_err(1.0, 1.0e-6) * d - one).absolute_error()
+        );
     }
-}
 
-impl NumCast for EFloat32 {
-    #[inline]
-    fn from<T: ToPrimitive>(n: T) -> Option<EFloat32> {
-        n.to_f32().map(|f| EFloat32 {
-            v: f,
-            low: f,
-            high: f,
-            #[cfg(debug_assertions)]
-            precise: f as f64,
-        })
+    #[test]
+    fn test_orientation() {
+        let e = EFloat32::new;
+        let (ax, ay, bx, by) = (e(0.0), e(0.0), e(1.0), e(0.0));
+        assert_eq!(
+            orientation(ax, ay, bx, by, e(0.5), e(1.0)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            orientation(ax, ay, bx, by, e(0.5), e(-1.0)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            orientation(ax, ay, bx, by, e(3.0), e(0.0)),
+            Some(Ordering::Equal)
+        );
+
+        // a hair off the line is still decided when the inputs are exact
+        assert_eq!(
+            orientation(ax, ay, bx, by, e(0.5), e(1.0e-30)),
+            Some(Ordering::Greater)
+        );
+
+        // but not when the points are uncertain by more than that
+        let fuzzy = EFloat32::new_with_err(0.0, 1.0e-6);
+        assert_eq!(orientation(ax, ay, bx, fuzzy, e(0.5), e(1.0e-9)), None);
+        assert_eq!(
+            orientation(ax, ay, bx, fuzzy, e(0.5), e(0.1)),
+            Some(Ordering::Greater)
+        );
     }
-}
 
-impl ApproxEq for EFloat32 {
-    type Margin = ();
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+    #[test]
+    fn test_with_precise() {
+        // A pipeline with one stage that claims a bound too tight for it: a
+        // value read back from a lossy source with an error claimed as 1e-4
+        // when the reference says it is really off by 1e-3.
+        let tenth = 0.1f32 as f64;
+        let reference = [tenth, 3.0 * tenth, 3.0 * tenth + 1.0e-3];
+        let stage0 = EFloat32::new(0.1);
+        let stage1 = stage0 * EFloat32::new(3.0);
+        let stage2 = EFloat32::new_with_err(stage1.value(), 1.0e-4);
 
-    fn approx_eq(&self, other: &Self, _margin: &()) -> bool {
-        // same logic as  !(self.high < other.low || other.high < self.low)
-        self.high >= other.low && other.high >= self.low
+        let stages = [stage0, stage1, stage2];
+        let first_bad = stages
+            .iter()
+            .zip(reference.iter())
+            .position(|(s, &r)| !s.with_precise(r).check_against_precise());
+        assert_eq!(first_bad, Some(2));
+
+        let good = stage1.with_precise(reference[1]);
+        assert_eq!(good.precise(), reference[1]);
+        assert_eq!(good.into_parts(), stage1.into_parts());
     }
-}
 
-fn f32_to_bits(f: f32) -> u32 {
-    unsafe { ::std::mem::transmute(f) }
-}
+    #[test]
+    fn test_div_scaled() {
+        let (big, small) = (EFloat32::new(1.0e20), EFloat32::new(1.0e-20));
 
-fn bits_to_f32(u: u32) -> f32 {
-    unsafe { ::std::mem::transmute(u) }
-}
+        // 1e-40 is subnormal: plain division rounds it and then widens
+        let plain = small / big;
+        let scaled = small.div_scaled(big);
+        assert_eq!(scaled.value(), plain.value());
+        assert!(scaled.representable_count() <= 2);
+        assert!(scaled.representable_count() < plain.representable_count());
+        assert!(scaled.lower_bound() as f64 <= 1.0e-40 && 1.0e-40 <= scaled.upper_bound() as f64);
 
-pub fn next_f32_up(f: f32) -> f32 {
-    if f.is_infinite() && f > 0.0 {
-        f
-    } else if f == -0.0 && f.is_sign_negative() {
-        0.0
-    } else {
-        let mut u = f32_to_bits(f);
-        if f >= 0.0 {
-            u += 1;
-        } else {
-            u -= 1;
+        // 1e40 overflows either way, but no lower bound is lost
+        let plain = big / small;
+        let scaled = big.div_scaled(small);
+        assert_eq!(scaled.lower_bound(), f32::MAX);
+        assert_eq!(scaled.upper_bound(), f32::INFINITY);
+        assert!(scaled.lower_bound() >= plain.lower_bound());
+
+        // in the normal range it is never looser than plain division
+        let a = EFloat32::new_with_err(3.7, 1.0e-3);
+        let b = EFloat32::new(-0.3);
+        assert!(EFloat32::new(1.0).div_scaled(b).representable_count() <= 2);
+        assert!(a.div_scaled(b).absolute_error() <= (a / b).absolute_error());
+        assert_eq!(a.div_scaled(b).value(), (a / b).value());
+
+        // a divisor straddling zero falls back
+        let z = EFloat32::new_with_err(0.0, 0.1);
+        assert_eq!(a.div_scaled(z).into_parts(), (a / z).into_parts());
+    }
+
+    #[test]
+    fn test_scale_pow2() {
+        let x = EFloat32::new_with_err(1.7, 1.0e-4) * EFloat32::new(0.3);
+        for &exp in [1, -1, 10, -20, 100].iter() {
+            let scaled = x.scale_pow2(exp);
+            assert_eq!(scaled.representable_count(), x.representable_count());
+            assert_eq!(scaled.value(), x.value() * 2f32.powi(exp));
+            assert_eq!(
+                scaled.relative_error_estimate(),
+                x.relative_error_estimate()
+            );
         }
-        bits_to_f32(u)
+        // where `*` widens
+        assert!((x * EFloat32::new(4.0)).representable_count() > x.representable_count());
+        assert_eq!(x.scale_pow2(3).scale_pow2(-3).into_parts(), x.into_parts());
+
+        let huge = x.scale_pow2(200);
+        assert_eq!(huge.upper_bound(), f32::INFINITY);
+        assert_eq!(huge.lower_bound(), f32::MAX);
+        let tiny = x.scale_pow2(-1000);
+        assert!(tiny.lower_bound() == 0.0 && tiny.upper_bound() > 0.0);
     }
-}
 
-pub fn next_f32_down(f: f32) -> f32 {
-    if f.is_infinite() && f < 0.0 {
-        f
-    } else if f == 0.0 && f.is_sign_positive() {
-        -0.0
-    } else {
-        let mut u = f32_to_bits(f);
-        if f <= -0.0 {
-            u += 1;
-        } else {
-            u -= 1;
+    #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+    #[test]
+    fn test_oracle_tightness() {
+        let x = EFloat32::new_with_err(1.3, 1.0e-3);
+        let slack0 = x.oracle_tightness();
+        assert!(slack0 > 0.9e-3 && slack0 <= x.absolute_error() / 2.0);
+
+        // the bounds widen along a multiply chain, and so does the slack
+        let mut y = x;
+        for _ in 0..5 {
+            y = y * EFloat32::new(1.1);
         }
-        bits_to_f32(u)
+        let slack = y.oracle_tightness();
+        assert!(slack > slack0);
+        assert!(slack <= y.absolute_error() / 2.0);
+        assert_eq!(
+            slack,
+            (y.precise() - y.lower_bound() as f64).min(y.upper_bound() as f64 - y.precise()) as f32
+        );
+
+        // an exact value has no slack
+        assert_eq!(EFloat32::new(2.0).oracle_tightness(), 0.0);
     }
-}
 
-// Higham (2002, sect 3.1)
-//pub const MACHINE_EPSILON: f32 = ::std::f32::EPSILON * 0.5;
-//fn gamma(n: i32) -> f32 {
-//    (n as f32 * MACHINE_EPSILON) / (1.0 - n as f32 * MACHINE_EPSILON)
-//}
+    #[test]
+    fn test_modf() {
+        let x = EFloat32::new_with_err(3.25, 0.01);
+        let (int, frac) = x.modf();
+        assert_eq!(int.into_parts(), (3.0, 3.0, 3.0));
+        assert!(frac.lower_bound() <= 0.24 && frac.upper_bound() >= 0.26);
+        assert!(frac.absolute_error() <= x.absolute_error());
+        assert_eq!(frac.value(), 0.25);
+
+        let (int, frac) = EFloat32::new_with_err(-2.5, 0.1).modf();
+        assert_eq!((int.value(), frac.value()), (-2.0, -0.5));
+        assert!(int.is_exact() && frac.upper_bound() < 0.0);
 
-#[cfg(test)]
-mod test {
-    use super::*;
+        for &v in [2.0f32, -2.0, 0.0].iter() {
+            let x = EFloat32::new_with_err(v + 0.05, 0.1);
+            let (int, frac) = x.modf();
+            // trunc is zero on both sides of zero
+            assert_eq!(int.is_exact(), v == 0.0);
+            assert_eq!(int.value() + frac.value(), x.value());
+            let sum = int + frac;
+            assert!(sum.lower_bound() <= x.lower_bound() && x.upper_bound() <= sum.upper_bound());
+        }
+    }
 
     #[test]
-    fn test1() {
+    fn test_sqr_powi() {
+        let x = EFloat32::new_with_err(1.1, 1.0e-4);
+        let sq = x.sqr();
+        assert_eq!(sq.into_parts(), (x * x).into_parts());
+        let straddling = EFloat32::new_with_err(0.5, 1.0);
+        assert_eq!(straddling.sqr().lower_bound(), 0.0);
+        assert!((straddling * straddling).lower_bound() < 0.0);
+        assert!(EFloat32::new_with_err(-2.0, 0.1).sqr().lower_bound() >= 3.6);
+
+        let repeated = |x: EFloat32| (1..16).fold(x, |acc, _| acc * x);
+
+        // for a positive base, 4 squarings round about as much as 15
+        // multiplications (each rounding is amplified by the later squarings)
+        let squared = x.powi(16);
+        let naive = repeated(x);
+        assert!((squared.value() - naive.value()).abs() < 1.0e-5);
+        assert!((squared.lower_bound() - naive.lower_bound()).abs() < 1.0e-5);
+        assert!((squared.upper_bound() - naive.upper_bound()).abs() < 1.0e-5);
+
+        // but straddling zero, the multiplications let the sign flip at
+        // every step, and the squarings don't
+        let squared = straddling.powi(16);
+        let naive = repeated(straddling);
+        assert_eq!(squared.lower_bound(), 0.0);
+        assert!(naive.lower_bound() < -100.0);
+        assert!(squared.upper_bound() <= naive.upper_bound() * (1.0 + 1.0e-6));
+        assert!(squared.upper_bound() >= 1.5f32.powi(16));
+
+        for n in -7..8 {
+            let p = x.powi(n);
+            let exact = (x.value() as f64).powi(n);
+            assert!(p.lower_bound() as f64 <= exact && exact <= p.upper_bound() as f64);
+        }
+        let odd = EFloat32::new_with_err(-1.5, 0.1).powi(5);
+        assert!(odd.upper_bound() < 0.0);
+        assert!(straddling.powi(6).lower_bound() >= 0.0);
+    }
+
+    #[test]
+    fn test_linear_combination() {
+        // a 5-tap smoothing filter
+        let weights = [0.0625f32, 0.25, 0.375, 0.25, 0.0625];
+        let samples = [0.3f32, 1.7, -0.4, 2.2, 0.9];
+        let terms: Vec<(f32, EFloat32)> = weights
+            .iter()
+            .zip(samples.iter())
+            .map(|(&w, &x)| (w, EFloat32::new_with_err(x, 1.0e-3)))
+            .collect();
+        let combined = linear_combination(&terms);
+        let composed: EFloat32 = terms.iter().map(|&(w, x)| EFloat32::new(w) * x).sum();
+        let expected: f64 = weights
+            .iter()
+            .zip(samples.iter())
+            .map(|(&w, &x)| w as f64 * x as f64)
+            .sum();
+        assert!((combined.value() as f64 - expected).abs() < 1.0e-6);
+        assert!(
+            combined.lower_bound() as f64 <= expected && expected <= combined.upper_bound() as f64
+        );
+        assert!(combined.lower_bound() >= composed.lower_bound());
+        assert!(combined.upper_bound() <= composed.upper_bound());
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        assert!(combined.check_against_precise());
+
+        assert!(linear_combination(&[]).is_exact_zero());
+    }
+
+    #[test]
+    fn test_double_halve() {
+        let x = EFloat32::new(3.0) / EFloat32::new(7.0);
+        let (v, low, high) = x.into_parts();
+        assert_eq!(x.double().into_parts(), (v * 2.0, low * 2.0, high * 2.0));
+        assert_eq!(x.halve().into_parts(), (v * 0.5, low * 0.5, high * 0.5));
+        assert_eq!(x.double().halve().into_parts(), x.into_parts());
+        assert!(EFloat32::new(1.5).double().is_exact());
+
+        // overflow keeps a finite lower bound but an infinite upper one
+        let big = EFloat32::new(f32::MAX);
+        let d = big.double();
+        assert_eq!(d.lower_bound(), f32::MAX);
+        assert_eq!(d.upper_bound(), f32::INFINITY);
+
+        // halving the smallest subnormal loses it, so the bounds bracket it
+        let tiny = EFloat32::new(1.0e-45);
+        let h = tiny.halve();
+        assert_eq!(h.lower_bound(), 0.0);
+        assert_eq!(h.upper_bound(), 1.0e-45);
+        // but halving an even subnormal is exact
+        assert!(EFloat32::new(2.0e-45 * 2.0).halve().is_exact());
+    }
+
+    #[test]
+    fn test_compare_definite() {
+        let a = EFloat32::new_with_err(1.0, 0.25);
+        let b = EFloat32::new_with_err(2.0, 0.25);
+        assert_eq!(a.compare_definite(&b), Some(Ordering::Less));
+        assert_eq!(b.compare_definite(&a), Some(Ordering::Greater));
+
+        // touching at a shared bound is ambiguous, as for `overlaps`
+        let below = EFloat32::new_from_rounded(1.0, RoundingMode::Up);
+        let above = EFloat32::new_from_rounded(1.0, RoundingMode::Down);
+        assert!(below.overlaps(&above));
+        assert_eq!(below.compare_definite(&above), None);
+        assert_eq!(above.compare_definite(&below), None);
+
+        let d = EFloat32::new_with_err(1.5, 1.0);
+        assert_eq!(a.compare_definite(&d), None);
+        assert_eq!(d.compare_definite(&b), None);
+        assert_eq!(
+            EFloat32::new(1.0).compare_definite(&EFloat32::new(1.0)),
+            None
+        );
+        assert_eq!(EFloat32::new(f32::NAN).compare_definite(&a), None);
+    }
+
+    #[test]
+    fn test_rsqrt() {
+        for &x in [2.0f32, 0.3, 100.0, 1e-20].iter() {
+            let e = EFloat32::new_with_err(x, x * 1e-6);
+            let r = e.rsqrt();
+            let composed = e.sqrt().recip();
+            assert!(composed.lower_bound() < r.lower_bound());
+            assert!(r.upper_bound() < composed.upper_bound());
+            let expected = 1.0 / (x as f64).sqrt();
+            assert!((r.lower_bound() as f64) < expected && expected < r.upper_bound() as f64);
+        }
+        let r = EFloat32::new(4.0).rsqrt();
+        assert_eq!(r.value(), 0.5);
+        assert!(r.lower_bound() <= 0.5 && 0.5 <= r.upper_bound());
+
+        let r = EFloat32::new_with_err(0.5, 1.0).rsqrt();
+        assert_eq!(r.upper_bound(), f32::INFINITY);
+        assert!(EFloat32::new_with_err(-4.0, 1.0)
+            .rsqrt()
+            .lower_bound()
+            .is_nan());
+    }
+
+    #[test]
+    fn test_mul_exact_zero() {
+        let unbounded = EFloat32::new(1.0) / EFloat32::new_with_err(0.0, 0.5);
+        assert!(unbounded.upper_bound().is_infinite());
+        let p = EFloat32::zero() * unbounded;
+        assert_eq!(p.into_parts(), (0.0, 0.0, 0.0));
+        let p = unbounded * EFloat32::zero();
+        assert_eq!(p.into_parts(), (0.0, 0.0, 0.0));
+        assert!(p.is_exact());
+
+        // a zero that is not exact still multiplies out by corners
+        let p = EFloat32::new_with_err(0.0, 0.1) * EFloat32::new(2.0);
+        assert!(!p.is_exact());
+    }
+
+    #[test]
+    fn test_indeterminate_forms() {
+        let inf = EFloat32::new(f32::INFINITY);
+        let neg_inf = EFloat32::new(f32::NEG_INFINITY);
+        let everything = (f32::NEG_INFINITY, f32::INFINITY);
+
+        let sum = inf + neg_inf;
+        assert!(sum.value().is_nan());
+        assert_eq!((sum.lower_bound(), sum.upper_bound()), everything);
+        assert!(!sum.is_degenerate());
+
+        let diff = inf - inf;
+        assert!(diff.value().is_nan());
+        assert_eq!((diff.lower_bound(), diff.upper_bound()), everything);
+
+        let quot = inf / inf;
+        assert!(quot.value().is_nan());
+        assert_eq!((quot.lower_bound(), quot.upper_bound()), everything);
+
+        // an exact zero wins over an infinity
+        assert_eq!((inf * EFloat32::zero()).into_parts(), (0.0, 0.0, 0.0));
+        // but not over a NaN, and it keeps the sign of the product
+        let p = EFloat32::nan() * EFloat32::zero();
+        assert!(p.value().is_nan());
+        let p = EFloat32::zero() * EFloat32::nan();
+        assert!(p.value().is_nan());
+        assert!(!(p.lower_bound() == 0.0 && p.upper_bound() == 0.0));
+        let p = EFloat32::new(-0.0) * EFloat32::new(3.0);
+        assert!(p.value() == 0.0 && p.value().is_sign_negative());
+        assert!(p.is_exact());
+        // and a zero bound times an infinite one drops out, leaving the
+        // corners that are defined
+        let p = EFloat32::from_parts(0.5, 0.0, 1.0) * inf;
+        assert!(!p.is_degenerate());
+        assert_eq!(p.upper_bound(), f32::INFINITY);
+
+        // the fast path follows the same policy
+        let fast = inf.fast() + neg_inf.fast();
+        assert_eq!((fast.lower_bound(), fast.upper_bound()), everything);
+        let fast = inf.fast() / inf.fast();
+        assert_eq!((fast.lower_bound(), fast.upper_bound()), everything);
+        let fast = EFloat32::nan().fast() * EFloat32::zero().fast();
+        assert!(fast.value().is_nan());
+    }
+
+    #[test]
+    fn test_tighter_of() {
+        let x = EFloat32::new_with_err(0.1, 0.001);
+        // x^2 - 1, computed two ways
+        let a = x * x - EFloat32::one();
+        let b = (x - EFloat32::one()) * (x + EFloat32::one());
+        let t = tighter_of(a, b);
+        assert!(t.absolute_error() <= a.absolute_error());
+        assert!(t.absolute_error() <= b.absolute_error());
+        assert_eq!(tighter_of(b, a).into_parts(), t.into_parts());
+
+        let wide = EFloat32::new_with_err(2.0, 0.5);
+        let narrow = EFloat32::new_with_err(2.1, 0.1);
+        assert_eq!(tighter_of(wide, narrow).into_parts(), narrow.into_parts());
+        assert_eq!(tighter_of(narrow, wide).into_parts(), narrow.into_parts());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic]
+    fn test_tighter_of_disjoint() {
+        tighter_of(EFloat32::new(1.0), EFloat32::new(2.0));
+    }
+
+    #[test]
+    fn test_interval_min_max() {
+        assert!(interval_min(Vec::new()).is_none());
+        assert!(interval_max(Vec::new()).is_none());
+
+        // 1.0 and 1.1 overlap, so either could be the smallest
+        let values = vec![
+            EFloat32::from_parts(1.0, 0.8, 1.2),
+            EFloat32::from_parts(1.1, 0.9, 1.15),
+            EFloat32::from_parts(5.0, 4.5, 5.5),
+        ];
+        let min = interval_min(values.clone()).unwrap();
+        assert_eq!(min.into_parts(), (1.0, 0.8, 1.15));
+        let max = interval_max(values.clone()).unwrap();
+        assert_eq!(max.into_parts(), (5.0, 4.5, 5.5));
+
+        // the maximum is ambiguous between the last two
+        let values = vec![
+            EFloat32::from_parts(1.0, 0.8, 1.2),
+            EFloat32::from_parts(5.0, 4.0, 6.0),
+            EFloat32::from_parts(5.2, 4.9, 5.3),
+        ];
+        let max = interval_max(values).unwrap();
+        assert_eq!(max.into_parts(), (5.2, 4.9, 6.0));
+    }
+
+    #[cfg(feature = "infinity-events")]
+    #[test]
+    fn test_infinity_events() {
+        reset_infinity_events();
+        let a = EFloat32::new(1.0);
+        let b = EFloat32::new_with_err(0.0, 0.1);
+        let c = a + a * a;
+        assert_eq!(infinity_events(), 0);
+        let d = c / b;
+        assert_eq!(infinity_events(), 1);
+        // already infinite, so not a new event
+        let _ = d * c + a;
+        assert_eq!(infinity_events(), 1);
+        let _ = b.recip();
+        assert_eq!(infinity_events(), 2);
+    }
+
+    #[test]
+    fn test_classify_interval() {
+        let min = f32::MIN_POSITIVE;
+        let e = EFloat32::from_parts(min, next_f32_down(min), next_f32_up(min));
+        assert_eq!(e.classify(), FpCategory::Normal);
+        assert_eq!(
+            e.classify_interval(),
+            (FpCategory::Subnormal, FpCategory::Normal)
+        );
+
+        let e = EFloat32::new_with_err(1.0, 0.5);
+        assert_eq!(
+            e.classify_interval(),
+            (FpCategory::Normal, FpCategory::Normal)
+        );
+        let e = EFloat32::from_parts(0.0, 0.0, 1.0) / EFloat32::from_parts(1.0, 0.0, 1.0);
+        assert_eq!(e.classify_interval().1, FpCategory::Infinite);
+    }
+
+    #[test]
+    fn test_mul_add_f32() {
+        let cases = [
+            (EFloat32::new_with_err(0.7, 0.01), 1.3f32, -0.4f32),
+            (EFloat32::new_with_err(0.7, 0.01), -1.3, 0.4),
+            (EFloat32::new_with_err(-2.5, 0.001), 0.1, 0.25),
+            (EFloat32::new(3.0), 2.0, 1.0),
+        ];
+        for &(x, a, b) in cases.iter() {
+            let tight = x.mul_add_f32(a, b);
+            let wrapped = x.mul_add(EFloat32::new(a), EFloat32::new(b));
+            assert_eq!(tight.value(), wrapped.value());
+            assert!(wrapped.lower_bound() <= tight.lower_bound());
+            assert!(tight.upper_bound() <= wrapped.upper_bound());
+        }
+        // exact inputs and an exact result give an exact interval
+        let e = EFloat32::new(3.0).mul_add_f32(2.0, 1.0);
+        assert_eq!(e.into_parts(), (7.0, 7.0, 7.0));
+    }
+
+    #[test]
+    fn test_one_minus() {
+        // as in test1
         let x = EFloat32::new(0.87234);
         let y = EFloat32::new(0.2348709);
         let z = x * y;
-        let w = EFloat32::new(1.0) - z;
+        let general = EFloat32::new(1.0) - z;
+        let w = z.one_minus();
         println!(
-            "value={} upper={} lower={} absolute_error={} relative_error={} precise={}",
+            "one_minus: value={} lower={} upper={}, general: lower={} upper={}",
             w.value(),
-            w.upper_bound(),
             w.lower_bound(),
-            w.absolute_error(),
-            w.relative_error(),
-            w.precise()
+            w.upper_bound(),
+            general.lower_bound(),
+            general.upper_bound()
+        );
+        assert_eq!(w.value(), general.value());
+        assert!(general.lower_bound() <= w.lower_bound());
+        assert!(w.upper_bound() <= general.upper_bound());
+
+        // near 1 the subtraction is exact
+        let near_one = EFloat32::from_parts(0.999, 0.998, 0.9995);
+        let w = near_one.one_minus();
+        assert_eq!(w.lower_bound(), 1.0 - 0.9995f32);
+        assert_eq!(w.upper_bound(), 1.0 - 0.998f32);
+        let general = EFloat32::new(1.0) - near_one;
+        assert!(general.lower_bound() < w.lower_bound());
+        assert!(w.upper_bound() < general.upper_bound());
+
+        // tiny values, where 1 - x is not representable
+        let w = EFloat32::new(1e-10).one_minus();
+        assert!(w.lower_bound() < 1.0 && w.upper_bound() == 1.0);
+    }
+
+    #[test]
+    fn test_step() {
+        let edge = EFloat32::new_with_err(0.5, 0.01);
+        let s = step(edge, EFloat32::new_with_err(0.6, 0.01));
+        assert_eq!(s.into_parts(), (1.0, 1.0, 1.0));
+        let s = step(edge, EFloat32::new_with_err(0.4, 0.01));
+        assert_eq!(s.into_parts(), (0.0, 0.0, 0.0));
+        let s = step(edge, EFloat32::new_with_err(0.505, 0.01));
+        assert_eq!(s.into_parts(), (1.0, 0.0, 1.0));
+        let s = step(edge, EFloat32::new_with_err(0.495, 0.01));
+        assert_eq!(s.into_parts(), (0.0, 0.0, 1.0));
+        // exactly on an exact edge counts as at or above it
+        let s = step(EFloat32::new(0.5), EFloat32::new(0.5));
+        assert_eq!(s.into_parts(), (1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_smoothstep() {
+        let (e0, e1) = (EFloat32::new(1.0), EFloat32::new(3.0));
+        // the subtraction widens t slightly past the edges
+        let s = smoothstep(e0, e1, EFloat32::new(1.0));
+        assert_eq!((s.value(), s.lower_bound()), (0.0, 0.0));
+        assert!(s.upper_bound() < 1e-30);
+        let s = smoothstep(e0, e1, EFloat32::new(3.0));
+        assert_eq!((s.value(), s.upper_bound()), (1.0, 1.0));
+        assert!(s.lower_bound() > 0.999999);
+        let s = smoothstep(e0, e1, EFloat32::new(0.5));
+        assert_eq!(s.into_parts(), (0.0, 0.0, 0.0));
+        let s = smoothstep(e0, e1, EFloat32::new(3.5));
+        assert_eq!(s.into_parts(), (1.0, 1.0, 1.0));
+
+        let s = smoothstep(e0, e1, EFloat32::new(2.0));
+        assert_eq!(s.value(), 0.5);
+        assert!(s.lower_bound() < 0.5 && 0.5 < s.upper_bound());
+        assert!(s.absolute_error() < 1e-6);
+
+        // straddling the lower edge
+        let s = smoothstep(e0, e1, EFloat32::new_with_err(1.0, 0.2));
+        assert_eq!(s.lower_bound(), 0.0);
+        assert!(s.upper_bound() > 0.028 && s.upper_bound() < 0.0281);
+
+        // straddling the upper edge, with uncertain edges
+        let s = smoothstep(
+            EFloat32::new_with_err(1.0, 0.01),
+            EFloat32::new_with_err(3.0, 0.01),
+            EFloat32::new_with_err(3.0, 0.2),
+        );
+        assert_eq!(s.upper_bound(), 1.0);
+        assert!(s.lower_bound() > 0.96 && s.lower_bound() < 0.97);
+    }
+
+    #[test]
+    fn test_describe() {
+        let x = EFloat32::from_parts(0.25, 0.2499, 0.2502);
+        let d = x.describe();
+        assert!(d.starts_with("0.25 (±2.0e-4, ~"), "{}", d);
+        assert!(d.ends_with(&format!("~{} significant bits)", x.significant_bits())));
+        assert_eq!(x.significant_bits(), 10);
+        assert_eq!(
+            EFloat32::new(1.5).describe(),
+            "1.5 (±0.0e0, ~24 significant bits)"
+        );
+        assert_ne!(x.describe(), x.to_string());
+    }
+
+    #[test]
+    fn test_constrain_sum() {
+        let (a0, b0) = (
+            EFloat32::from_parts(0.25, 0.2, 0.26),
+            EFloat32::from_parts(0.75, 0.7, 0.78),
+        );
+        let (mut a, mut b) = (a0, b0);
+        constrain_sum(&mut a, &mut b, 1.0);
+        assert!(a.lower_bound() > a0.lower_bound() && a.upper_bound() == a0.upper_bound());
+        assert!(b.lower_bound() > b0.lower_bound() && b.upper_bound() == b0.upper_bound());
+        assert!(a.lower_bound() <= 0.25 && 0.25 <= a.upper_bound());
+        assert!(b.lower_bound() <= 0.75 && 0.75 <= b.upper_bound());
+        assert!((a.lower_bound() - 0.22).abs() < 1.0e-6);
+        assert!((b.lower_bound() - 0.74).abs() < 1.0e-6);
+        assert_eq!((a.value(), b.value()), (0.25, 0.75));
+
+        // already consistent intervals are left alone
+        let (mut c, mut d) = (
+            EFloat32::new_with_err(0.5, 0.1),
+            EFloat32::new_with_err(0.5, 0.1),
+        );
+        constrain_sum(&mut c, &mut d, 1.0);
+        assert_eq!(
+            c.into_parts(),
+            EFloat32::new_with_err(0.5, 0.1).into_parts()
+        );
+        assert_eq!(
+            d.into_parts(),
+            EFloat32::new_with_err(0.5, 0.1).into_parts()
+        );
+    }
+
+    #[test]
+    fn test_wrap_angle() {
+        // already in range: untouched
+        let a = EFloat32::new_with_err(1.0, 0.01);
+        let w = a.wrap_angle();
+        assert_eq!(w.into_parts(), a.into_parts());
+
+        // 1 + 3 turns comes back to 1, still tight
+        let b = EFloat32::new_with_err(1.0 + 6.0 * ::std::f32::consts::PI, 0.01);
+        let w = b.wrap_angle();
+        assert!((w.value() - 1.0).abs() < 1.0e-5);
+        assert!(w.lower_bound() <= 0.99 && w.upper_bound() >= 1.01);
+        assert!(w.absolute_error() < b.absolute_error() + 1.0e-5);
+
+        // just past π wraps to just past -π
+        let c = EFloat32::new_with_err(3.5, 0.1).wrap_angle();
+        let expected = 3.5 - 2.0 * ::std::f32::consts::PI;
+        assert!((c.value() - expected).abs() < 1.0e-6);
+        assert!(c.lower_bound() <= expected - 0.1 && c.upper_bound() >= expected + 0.1);
+        assert!(c.upper_bound() < -2.0);
+
+        // straddling π, or wider than a full turn, could be anywhere
+        for x in &[
+            EFloat32::new_with_err(3.1, 0.1),
+            EFloat32::new_with_err(0.0, 4.0),
+        ] {
+            let w = x.wrap_angle();
+            assert!(w.lower_bound() <= -::std::f32::consts::PI);
+            assert!(w.upper_bound() >= ::std::f32::consts::PI);
+            assert!(w.lower_bound() <= w.value() && w.value() <= w.upper_bound());
+        }
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn test_error_warning_threshold() {
+        use log::{Log, Metadata, Record};
+        use std::sync::Mutex;
+
+        struct Capture(Mutex<Vec<String>>);
+        impl Log for Capture {
+            fn enabled(&self, _: &Metadata) -> bool {
+                true
+            }
+            fn log(&self, record: &Record) {
+                if record.level() == ::log::Level::Warn {
+                    self.0.lock().unwrap().push(format!("{}", record.args()));
+                }
+            }
+            fn flush(&self) {}
+        }
+        static LOGGER: Capture = Capture(Mutex::new(Vec::new()));
+        ::log::set_logger(&LOGGER).unwrap();
+        ::log::set_max_level(::log::LevelFilter::Warn);
+
+        let a = EFloat32::new_with_err(1.001, 1.0e-5);
+        let b = EFloat32::new(1.0);
+
+        // nothing is logged until a threshold is set
+        let _ = a - b;
+        assert!(LOGGER.0.lock().unwrap().is_empty());
+
+        set_error_warning_threshold(1.0e-3);
+        assert_eq!(error_warning_threshold(), 1.0e-3);
+        let _ = a * b;
+        assert!(LOGGER.0.lock().unwrap().is_empty());
+        let diff = a - b;
+        {
+            let logged = LOGGER.0.lock().unwrap();
+            assert_eq!(logged.len(), 1);
+            assert!(
+                logged[0].starts_with("sub: relative error"),
+                "{}",
+                logged[0]
+            );
+            assert!(logged[0].contains(&format!(
+                "[{}, {}]",
+                diff.lower_bound(),
+                diff.upper_bound()
+            )));
+        }
+        set_error_warning_threshold(f32::INFINITY);
+    }
+
+    #[test]
+    fn test_eval_piecewise() {
+        let xs = [0.0f32, 1.0, 2.0, 4.0];
+        let ys = [
+            EFloat32::new(0.0),
+            EFloat32::new(2.0),
+            EFloat32::new(1.0),
+            EFloat32::new_with_err(3.0, 0.01),
+        ];
+
+        // inside one segment
+        let y = eval_piecewise(EFloat32::new_with_err(0.5, 0.1), &xs, &ys);
+        assert!((y.value() - 1.0).abs() < 1.0e-6);
+        assert!(y.lower_bound() <= 0.8 && 1.2 <= y.upper_bound());
+        assert!(y.absolute_error() < 0.41);
+
+        // straddling the peak at x = 1: the top of the kink is included, and
+        // both slopes bound the bottom
+        let y = eval_piecewise(EFloat32::new_with_err(0.95, 0.1), &xs, &ys);
+        assert!((y.value() - 1.9).abs() < 1.0e-5);
+        assert!(y.upper_bound() >= 2.0 && y.upper_bound() < 2.001);
+        assert!(y.lower_bound() <= 1.7 && y.lower_bound() > 1.69);
+
+        // beyond the table it is constant
+        let y = eval_piecewise(EFloat32::new_with_err(-3.0, 0.5), &xs, &ys);
+        assert_eq!(y.value(), 0.0);
+        assert!(y.absolute_error() < 1.0e-30);
+        let y = eval_piecewise(EFloat32::new(5.0), &xs, &ys);
+        assert!(y.lower_bound() <= 2.99 && 3.01 <= y.upper_bound());
+        #[cfg(any(debug_assertions, feature = "precise-tracking"))]
+        assert!(y.check_against_precise());
+
+        assert_eq!(
+            eval_piecewise(EFloat32::new(1.0), &[0.0], &ys[1..2]).value(),
+            2.0
         );
     }
 }
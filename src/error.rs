@@ -0,0 +1,52 @@
+use std::error::Error;
+use std::fmt;
+
+/// Why a checked `EFloat32` operation (such as `try_div`) could not produce a
+/// useful interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EFloatError {
+    /// The divisor is exactly zero.
+    DivisorIsZero,
+    /// The divisor's interval includes zero, so the quotient is unbounded.
+    DivisorStraddlesZero,
+    /// The result's bounds are infinite or NaN (e.g. from overflow, or from
+    /// operands that were already non-finite).
+    NonFinite,
+}
+
+impl fmt::Display for EFloatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match *self {
+            EFloatError::DivisorIsZero => "divisor is zero",
+            EFloatError::DivisorStraddlesZero => "divisor interval includes zero",
+            EFloatError::NonFinite => "result bounds are not finite",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for EFloatError {}
+
+/// Why `EFloat32::to_probability` rejected a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbabilityError {
+    /// The whole interval is below zero, which rounding alone can't explain.
+    BelowZero,
+    /// The whole interval is above one, which rounding alone can't explain.
+    AboveOne,
+    /// The value or its bounds are NaN.
+    NaN,
+}
+
+impl fmt::Display for ProbabilityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match *self {
+            ProbabilityError::BelowZero => "probability interval is entirely below zero",
+            ProbabilityError::AboveOne => "probability interval is entirely above one",
+            ProbabilityError::NaN => "probability is NaN",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl Error for ProbabilityError {}
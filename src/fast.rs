@@ -0,0 +1,197 @@
+use efloat32::{next_f32_down, next_f32_up, widen_indeterminate, EFloat32};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A stripped down `EFloat32` for hot loops.  It computes exactly the same
+/// value and bounds as `EFloat32` does for the basic arithmetic operators,
+/// but never carries the `precise: f64` shadow or calls `check()`, even with
+/// debug assertions on, so those values skip the debug oracle entirely.
+///
+/// Get one with `EFloat32::fast()`, and convert back with `to_efloat32()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EFloat32Fast {
+    v: f32,
+    low: f32,
+    high: f32,
+}
+
+impl EFloat32Fast {
+    /// Convert back into an `EFloat32`.  The precise shadow (if compiled in)
+    /// starts over from the value, as in `EFloat32::from_parts`, so it no
+    /// longer reflects anything that happened on the fast path.  A NaN
+    /// result (e.g. from `0/0`) converts to the same NaN `EFloat32` the
+    /// checked operators would have produced.
+    pub fn to_efloat32(self) -> EFloat32 {
+        EFloat32::from_parts_unchecked(self.v, self.low, self.high)
+    }
+
+    pub fn value(&self) -> f32 {
+        self.v
+    }
+
+    pub fn upper_bound(&self) -> f32 {
+        self.high
+    }
+
+    pub fn lower_bound(&self) -> f32 {
+        self.low
+    }
+}
+
+impl EFloat32 {
+    /// Drop precise tracking and checks, for performance-critical inner
+    /// loops.  See `EFloat32Fast`.
+    pub fn fast(self) -> EFloat32Fast {
+        let (v, low, high) = self.into_parts();
+        EFloat32Fast { v, low, high }
+    }
+}
+
+impl Add for EFloat32Fast {
+    type Output = EFloat32Fast;
+
+    fn add(self, other: EFloat32Fast) -> EFloat32Fast {
+        let (low, high) = widen_indeterminate(
+            next_f32_down(self.low + other.low),
+            next_f32_up(self.high + other.high),
+        );
+        EFloat32Fast {
+            v: self.v + other.v,
+            low,
+            high,
+        }
+    }
+}
+
+impl Sub for EFloat32Fast {
+    type Output = EFloat32Fast;
+
+    fn sub(self, other: EFloat32Fast) -> EFloat32Fast {
+        let (low, high) = widen_indeterminate(
+            next_f32_down(self.low - other.high),
+            next_f32_up(self.high - other.low),
+        );
+        EFloat32Fast {
+            v: self.v - other.v,
+            low,
+            high,
+        }
+    }
+}
+
+impl Mul for EFloat32Fast {
+    type Output = EFloat32Fast;
+
+    fn mul(self, other: EFloat32Fast) -> EFloat32Fast {
+        let has_nan = |x: &EFloat32Fast| x.v.is_nan() || x.low.is_nan() || x.high.is_nan();
+        if (self.low == 0.0 && self.high == 0.0 && !has_nan(&other))
+            || (other.low == 0.0 && other.high == 0.0 && !has_nan(&self))
+        {
+            let v = self.v * other.v;
+            return EFloat32Fast {
+                v: if v.is_nan() { 0.0 } else { v },
+                low: 0.0,
+                high: 0.0,
+            };
+        }
+        let prod: [f32; 4] = [
+            self.low * other.low,
+            self.high * other.low,
+            self.low * other.high,
+            self.high * other.high,
+        ];
+        let (low, high) = widen_indeterminate(
+            next_f32_down(prod[0].min(prod[1]).min(prod[2].min(prod[3]))),
+            next_f32_up(prod[0].max(prod[1]).max(prod[2].max(prod[3]))),
+        );
+        EFloat32Fast {
+            v: self.v * other.v,
+            low,
+            high,
+        }
+    }
+}
+
+impl Div for EFloat32Fast {
+    type Output = EFloat32Fast;
+
+    fn div(self, other: EFloat32Fast) -> EFloat32Fast {
+        if other.low < 0.0 && other.high > 0.0 {
+            // the divisor straddles zero, so return an interval of everything
+            return EFloat32Fast {
+                v: self.v / other.v,
+                low: -f32::INFINITY,
+                high: f32::INFINITY,
+            };
+        }
+        let prod: [f32; 4] = [
+            self.low / other.low,
+            self.high / other.low,
+            self.low / other.high,
+            self.high / other.high,
+        ];
+        let (low, high) = widen_indeterminate(
+            next_f32_down(prod[0].min(prod[1]).min(prod[2].min(prod[3]))),
+            next_f32_up(prod[0].max(prod[1]).max(prod[2].max(prod[3]))),
+        );
+        EFloat32Fast {
+            v: self.v / other.v,
+            low,
+            high,
+        }
+    }
+}
+
+impl Neg for EFloat32Fast {
+    type Output = EFloat32Fast;
+
+    fn neg(self) -> EFloat32Fast {
+        EFloat32Fast {
+            v: -self.v,
+            low: -self.high,
+            high: -self.low,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_fast_nan_converts_back() {
+        let zero = EFloat32::new(0.0);
+        let inf = EFloat32::new(f32::INFINITY);
+        let slow = zero / zero;
+        let fast = (zero.fast() / zero.fast()).to_efloat32();
+        assert!(fast.value().is_nan());
+        assert_eq!(
+            (fast.lower_bound(), fast.upper_bound()),
+            (slow.lower_bound(), slow.upper_bound())
+        );
+        assert!((inf.fast() - inf.fast()).to_efloat32().value().is_nan());
+    }
+
+    #[test]
+    fn test_fast_matches() {
+        let a = EFloat32::new_with_err(1.7, 0.01);
+        let b = EFloat32::new_with_err(-0.3, 0.002);
+        let c = EFloat32::new(2.5);
+
+        let slow = (a * b + c) / (a - c) - -b;
+        let fast = (a.fast() * b.fast() + c.fast()) / (a.fast() - c.fast()) - -b.fast();
+        assert_eq!(fast.to_efloat32().into_parts(), slow.into_parts());
+        assert_eq!(fast.value(), slow.value());
+        assert_eq!(fast.lower_bound(), slow.lower_bound());
+        assert_eq!(fast.upper_bound(), slow.upper_bound());
+
+        let slow = a / (b + EFloat32::new_with_err(0.3, 0.1));
+        let fast = a.fast() / (b.fast() + EFloat32::new_with_err(0.3, 0.1).fast());
+        assert_eq!(fast.lower_bound(), slow.lower_bound());
+        assert_eq!(fast.upper_bound(), slow.upper_bound());
+
+        let slow = EFloat32::new(0.0) * (a / (b + EFloat32::new_with_err(0.3, 0.1)));
+        let fast = EFloat32::new(0.0).fast()
+            * (a.fast() / (b.fast() + EFloat32::new_with_err(0.3, 0.1).fast()));
+        assert_eq!(fast.to_efloat32().into_parts(), slow.into_parts());
+    }
+}
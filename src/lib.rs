@@ -14,11 +14,44 @@
 //! * Operate on small numbers first, working up, so that the larger errors don't
 //!   propogate and grow as much.
 //!
+//! With debug assertions enabled, or with the `precise-tracking` feature, each
+//! value also carries a precise `f64` shadow of itself, and every operation
+//! asserts that its bounds still contain that shadow.
+//!
 //! Logic taken from pbrt-v3: https://github.com/mmp/pbrt-v3  (efloat.h class)
 //!   by Matt Pharr, Greg Humphreys, and Wenzel Jakob.
 
+// The bytemuck `Pod` impls, and the tests calling C's `fmodf`, are the only
+// unsafe code, and opt back in locally.
+#![cfg_attr(not(any(feature = "bytemuck", test)), forbid(unsafe_code))]
+#![cfg_attr(any(feature = "bytemuck", test), deny(unsafe_code))]
+
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
 extern crate float_cmp;
+#[cfg(feature = "half")]
+extern crate half;
+#[cfg(test)]
+extern crate libc;
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
+#[cfg(feature = "num-rational")]
+extern crate num_rational;
 extern crate num_traits;
+#[cfg(test)]
+extern crate proptest;
+#[cfg(feature = "rand")]
+extern crate rand;
 
 mod efloat32;
 pub use self::efloat32::*;
+
+mod bounds;
+pub use self::bounds::*;
+
+mod error;
+pub use self::error::*;
+
+mod fast;
+pub use self::fast::*;